@@ -33,7 +33,7 @@ fn main() {
 fn determine_git_version() -> String {
     handle_cmd_git_output(
         process::Command::new("git")
-            .args(&["rev-parse", "--short", "HEAD"])
+            .args(["rev-parse", "--short", "HEAD"])
             .output(),
     )
 }
@@ -29,44 +29,156 @@ use std::{
 };
 use wordle_site::wordle::*;
 
+///
+/// Usage: gen_all_data [words|default_state] [word size] [alphabet]
+///
+/// The word size defaults to 5 and the alphabet to english (the original wordle). The data for
+/// other word sizes and alphabets needs allowed words and frequency files for them in the data
+/// directory (see allowed_words_file_name and frequency_file_name).
+///
+/// When there is also an answers file (see answers_file_name), only those words are treated as
+/// possible answers in the generated default state data. When there is a past answers file (see
+/// past_answers_file_name), a second default state data file is generated for when none of the past
+/// answers can repeat.
+///
+/// The allowed words which look like plurals or past tenses (see Inflection) are flagged. Their base
+/// forms are usually shorter than the allowed words, so they are looked up in the base words file
+/// (see BASE_WORDS_FILE_NAME) as well as in the allowed words.
+///
+/// Everything is written by default, but the word lists and the default state data can also be
+/// written separately (see Stage). The build writes the word lists first, then fits the word prior
+/// & guess count model to them (with gen_word_prior & gen_guess_model), and then writes the default
+/// state data which depends on both.
+///
 fn main() {
-    do_all().expect("should work");
+    let mut args = std::env::args().skip(1).peekable();
+    let stage = match args.peek().map(String::as_str) {
+        Some("words") => Some(Stage::Words),
+        Some("default_state") => Some(Stage::DefaultState),
+        _ => None,
+    };
+    if stage.is_some() {
+        args.next();
+    }
+    let word_size = args
+        .next()
+        .map(|arg| arg.parse().expect("word size should be a number"))
+        .unwrap_or(WORD_SIZE);
+    let alphabet = args
+        .next()
+        .map(|arg| Alphabet::named(&arg).expect("should be a built-in alphabet"))
+        .unwrap_or_default();
+    let config = GameConfig::new(word_size, Some(NUM_TURNS))
+        .expect("should be a valid word size")
+        .with_alphabet(alphabet);
+    match stage {
+        Some(Stage::Words) => write_words(&config),
+        Some(Stage::DefaultState) => write_all_default_state_data(&config),
+        None => write_words(&config).and_then(|_| write_all_default_state_data(&config)),
+    }
+    .expect("should work");
+}
+
+///
+/// The files are written in two separate stages, because the other tools need the word lists, and
+/// the default state data needs the output of the other tools
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Stage {
+    /// The ordered allowed words, answers, past answers & inflections
+    Words,
+    /// The cached default state data (for every RepeatPolicy which needs it)
+    DefaultState,
+}
+
+fn write_words(config: &GameConfig) -> io::Result<()> {
+    let answers = read_answers(config)?;
+    let past_answers = read_past_answers(config)?;
+    // every answer must be guessable, even if the allowed words file forgot about it
+    let extra_allowed: Vec<String> = answers
+        .iter()
+        .flatten()
+        .cloned()
+        .chain(past_answers.iter().flatten().map(|past| past.word.clone()))
+        .collect();
+    write_ordered_allowed(config, &extra_allowed)?;
+    write_answers(config, answers.as_deref())?;
+    write_past_answers(config, past_answers.as_deref())?;
+    write_inflections(config, &extra_allowed)
 }
 
-fn do_all() -> io::Result<()> {
-    write_ordered_allowed()?;
-    write_default_state_data()?;
+fn write_all_default_state_data(config: &GameConfig) -> io::Result<()> {
+    write_default_state_data(config, false)?;
+    if read_past_answers(config)?.is_some() {
+        write_default_state_data(config, true)?;
+    } else {
+        remove_stale(&format!(
+            "{}{}",
+            EMBED_DATA_DIRECTORY,
+            no_repeats_default_state_data_file_name(config)
+        ))?;
+    }
     Ok(())
 }
 
-fn write_default_state_data() -> io::Result<()> {
-    let at = format!("{}{}", EMBED_DATA_DIRECTORY, DEFAULT_STATE_DATA_FILE_NAME);
+///
+/// Writes the default state data, either for the default RepeatPolicy or (when no_repeats is true)
+/// for when none of the past answers can be the answer again
+///
+fn write_default_state_data(config: &GameConfig, no_repeats: bool) -> io::Result<()> {
+    let file_name = if no_repeats {
+        no_repeats_default_state_data_file_name(config)
+    } else {
+        default_state_data_file_name(config)
+    };
+    let at = format!("{}{}", EMBED_DATA_DIRECTORY, file_name);
     // open the file to contain the cached default state data
     let mut f = fs::File::create(&at)?;
 
-    let (dur, out): (Duration, io::Result<()>) = timed(move || {
-        // compute the data we should put into the file, and write it...
-        for item in Solver::default().compute_top_k_guesses::<{ N_RECOMMENDATIONS }>() {
-            writeln!(
-                f,
-                "{} {} {} {}",
-                item.word, item.score.abs, item.score.expected_info, item.score.weight,
-            )?;
+    let strategies = builtin_scoring_strategies();
+    let (dur, out): (Duration, io::Result<()>) = timed(|| {
+        // compute the data we should put into the file (for every strategy), and write it...
+        let data = Data::read_with_config(config).map_err(io::Error::other)?;
+        let mut solver = Solver::with_config(*config, &data);
+        if no_repeats {
+            let cutoff = solver
+                .latest_past_answer_date()
+                .ok_or_else(|| io::Error::other("there are no past answers"))?;
+            solver.set_repeat_policy(RepeatPolicy::Exclude { cutoff });
+        }
+        solver.load_coloring_table();
+        let top_guesses =
+            solver.compute_default_state_guesses::<{ N_RECOMMENDATIONS }>(&strategies);
+        for (strategy, items) in strategies.iter().zip(top_guesses) {
+            for item in items {
+                writeln!(
+                    f,
+                    "{} {} {} {} {} {} {}",
+                    strategy.id(),
+                    item.word,
+                    item.score.abs,
+                    item.score.tie_break,
+                    item.score.expected_info,
+                    item.score.weight,
+                    item.score.worst_case,
+                )?;
+            }
         }
         Ok(())
     });
     out?;
     eprintln!(
-        "done! wrote {} recommendations to {} in {:.2}s",
+        "done! wrote {} recommendations for {} strategies to {} in {:.2}s",
         N_RECOMMENDATIONS,
+        strategies.len(),
         at,
         dur.as_secs_f64()
     );
     Ok(())
 }
 
-fn write_ordered_allowed() -> io::Result<()> {
-    let (dur, out) = timed(write_ordered_allowed_inner);
+fn write_ordered_allowed(config: &GameConfig, extra_allowed: &[String]) -> io::Result<()> {
+    let (dur, out) = timed(|| write_ordered_allowed_inner(config, extra_allowed));
     let (name, lines) = out?;
     eprintln!(
         "done! wrote {} words to {} in {:.2}s",
@@ -77,32 +189,160 @@ fn write_ordered_allowed() -> io::Result<()> {
     Ok(())
 }
 
-fn write_ordered_allowed_inner() -> io::Result<(String, usize)> {
-    let unordered = read_unordered_allowed_words()?;
-    let ordered = read_ordered_frequency_data_words()?;
-    let to_write = ordered_words(&unordered, &ordered).filter(|s| is_wordle_str(s));
+fn write_ordered_allowed_inner(
+    config: &GameConfig,
+    extra_allowed: &[String],
+) -> io::Result<(String, usize)> {
+    let mut unordered = read_unordered_allowed_words(config)?;
+    let mut allowed: HashSet<String> = unordered.iter().cloned().collect();
+    for word in extra_allowed {
+        if allowed.insert(word.clone()) {
+            unordered.push(word.clone());
+        }
+    }
+    let ordered = read_ordered_frequency_data_words(config)?;
+    let to_write = ordered_words(&unordered, &ordered).filter(|s| config.is_word(s));
+
+    let at = format!(
+        "{}{}",
+        EMBED_DATA_DIRECTORY,
+        ordered_allowed_words_file_name(config)
+    );
+    let count = write_compressed_words(config, &at, to_write)?;
+    Ok((at, count))
+}
+
+///
+/// Writes the answers (if there is an answers file), or removes the answers left over from a
+/// previous run (if there isn't one anymore)
+///
+fn write_answers(config: &GameConfig, answers: Option<&[String]>) -> io::Result<()> {
+    let at = format!(
+        "{}{}",
+        EMBED_DATA_DIRECTORY,
+        compressed_answers_file_name(config)
+    );
+    let answers = match answers {
+        Some(answers) => answers,
+        None => return remove_stale(&at),
+    };
+
+    let (dur, out) =
+        timed(|| write_compressed_words(config, &at, answers.iter().map(|answer| answer.as_str())));
+    eprintln!(
+        "done! wrote {} answers to {} in {:.2}s",
+        out?,
+        at,
+        dur.as_secs_f64()
+    );
+    Ok(())
+}
 
+///
+/// Writes the past answers (if there is a past answers file), or removes the past answers left over
+/// from a previous run (if there isn't one anymore)
+///
+fn write_past_answers(config: &GameConfig, past_answers: Option<&[PastAnswer]>) -> io::Result<()> {
     let at = format!(
         "{}{}",
-        EMBED_DATA_DIRECTORY, ORDERED_ALLOWED_WORDS_FILE_NAME
+        EMBED_DATA_DIRECTORY,
+        compressed_past_answers_file_name(config)
     );
+    let past_answers = match past_answers {
+        Some(past_answers) => past_answers,
+        None => return remove_stale(&at),
+    };
+
+    let (dur, out) = timed(|| -> io::Result<()> {
+        let mut out = io::BufWriter::new(fs::File::create(&at)?);
+        for past in past_answers {
+            out.write_all(&past.date.to_bytes())?;
+            out.write_all(&CompressedWord::new(config.alphabet(), &past.word).as_bytes())?;
+        }
+        Ok(())
+    });
+    out?;
+    eprintln!(
+        "done! wrote {} past answers to {} in {:.2}s",
+        past_answers.len(),
+        at,
+        dur.as_secs_f64()
+    );
+    Ok(())
+}
+
+///
+/// Writes the allowed words which are inflections of other words (see Inflection)
+///
+fn write_inflections(config: &GameConfig, extra_allowed: &[String]) -> io::Result<()> {
+    let at = format!(
+        "{}{}",
+        EMBED_DATA_DIRECTORY,
+        compressed_inflections_file_name(config)
+    );
+
+    let (dur, out) = timed(|| -> io::Result<usize> {
+        let mut known: HashSet<String> =
+            read_unordered_allowed_words(config)?.into_iter().collect();
+        known.extend(extra_allowed.iter().cloned());
+        // the base forms usually have fewer letters, so they can't be the allowed words
+        known.extend(read_base_words(config)?);
+        let mut words: Vec<&str> = known
+            .iter()
+            .map(|word| word.as_str())
+            .filter(|word| config.is_word(word))
+            .collect();
+        words.sort_unstable();
+
+        let inflections = find_inflections(&config.alphabet(), words, |word| known.contains(word));
+        let mut out = io::BufWriter::new(fs::File::create(&at)?);
+        for inflected in &inflections {
+            out.write_all(&CompressedWord::new(config.alphabet(), &inflected.word).as_bytes())?;
+            out.write_all(&[inflected.inflection.to_byte()])?;
+        }
+        Ok(inflections.len())
+    });
+    eprintln!(
+        "done! wrote {} inflected words to {} in {:.2}s",
+        out?,
+        at,
+        dur.as_secs_f64()
+    );
+    Ok(())
+}
+
+///
+/// Removes a generated file which shouldn't exist anymore (because it's input was removed)
+///
+fn remove_stale(at: &str) -> io::Result<()> {
+    match fs::remove_file(at) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn write_compressed_words<'a>(
+    config: &GameConfig,
+    at: &str,
+    words: impl Iterator<Item = &'a str>,
+) -> io::Result<usize> {
     let mut out = io::BufWriter::new(
         fs::File::options()
             .truncate(true)
             .create(true)
             .write(true)
-            .open(&at)?,
+            .open(at)?,
     );
 
     let mut count = 0;
-    for item in to_write {
-        let compressed = CompressedWord::new(item);
+    for item in words {
+        let compressed = CompressedWord::new(config.alphabet(), item);
         assert_eq!(compressed.to_string(), item);
         out.write_all(&compressed.as_bytes())?;
         count += 1;
     }
 
-    Ok((at, count))
+    Ok(count)
 }
 
 fn ordered_words<'a>(
@@ -128,21 +368,104 @@ fn ordered_words<'a>(
         )
 }
 
-fn read_unordered_allowed_words() -> io::Result<Vec<String>> {
-    let path = format!("{}{}", DATA_DIRECTORY, ALLOWED_WORDS_FILE_NAME);
+fn read_unordered_allowed_words(config: &GameConfig) -> io::Result<Vec<String>> {
+    let alphabet = config.alphabet();
+    let path = format!("{}{}", DATA_DIRECTORY, allowed_words_file_name(&alphabet));
     let f = fs::File::open(path)?;
     io::BufReader::new(f)
         .lines()
-        .map(|l| l.map(|l| normalize_wordle_word(&l)))
+        .map(|l| l.map(|l| alphabet.normalize(&l)))
         .collect()
 }
 
-fn read_ordered_frequency_data_words() -> io::Result<Vec<String>> {
-    let path = format!("{}{}", DATA_DIRECTORY, FREQUENCY_FILE_NAME);
+///
+/// Reads the base words file (if it exists), keeping the words of every length
+///
+fn read_base_words(config: &GameConfig) -> io::Result<Vec<String>> {
+    let alphabet = config.alphabet();
+    let path = format!("{}{}", DATA_DIRECTORY, BASE_WORDS_FILE_NAME);
+    let f = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    io::BufReader::new(f)
+        .lines()
+        .map(|l| l.map(|l| alphabet.normalize(&l)))
+        .filter(|l| !matches!(l, Ok(l) if l.is_empty()))
+        .collect()
+}
+
+///
+/// Reads the answers file (if it exists), skipping any words which don't fit the game
+///
+fn read_answers(config: &GameConfig) -> io::Result<Option<Vec<String>>> {
+    let alphabet = config.alphabet();
+    let path = format!("{}{}", DATA_DIRECTORY, answers_file_name(&alphabet));
+    let f = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let mut seen = HashSet::new();
+    let mut answers = Vec::new();
+    for line in io::BufReader::new(f).lines() {
+        let answer = alphabet.normalize(&line?);
+        if config.is_word(&answer) && seen.insert(answer.clone()) {
+            answers.push(answer);
+        }
+    }
+    Ok(Some(answers))
+}
+
+///
+/// Reads the past answers file (if it exists), skipping any words which don't fit the game. The
+/// past answers are sorted from oldest to newest.
+///
+fn read_past_answers(config: &GameConfig) -> io::Result<Option<Vec<PastAnswer>>> {
+    let alphabet = config.alphabet();
+    let path = format!("{}{}", DATA_DIRECTORY, past_answers_file_name(&alphabet));
+    let f = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let mut past_answers = Vec::new();
+    for line in io::BufReader::new(f).lines() {
+        let line = line?;
+        let (date, word) = match line.trim().split_once(' ') {
+            Some(parts) => parts,
+            None if line.trim().is_empty() => continue,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("malformed past answer '{}'", line),
+                ))
+            }
+        };
+
+        let date = date
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let word = alphabet.normalize(word);
+        if config.is_word(&word) {
+            past_answers.push(PastAnswer { date, word });
+        }
+    }
+    past_answers.sort_by_key(|past| past.date);
+    Ok(Some(past_answers))
+}
+
+fn read_ordered_frequency_data_words(config: &GameConfig) -> io::Result<Vec<String>> {
+    let alphabet = config.alphabet();
+    let path = format!("{}{}", DATA_DIRECTORY, frequency_file_name(config));
     let f = fs::File::open(path)?;
     io::BufReader::new(f)
         .lines()
-        .map(|l| l.map(|l| l.split(' ').next().map(normalize_wordle_word)))
+        .map(|l| l.map(|l| l.split(' ').next().map(|w| alphabet.normalize(w))))
         .filter_map(|l| match l {
             Ok(Some(v)) => Some(Ok(v)),
             Ok(None) => None,
@@ -0,0 +1,121 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::{
+    fs,
+    io::{self, Write},
+    time::Instant,
+};
+use wordle_site::wordle::*;
+
+// The answers for the simulated games are picked from this many of the most common words (the
+// embedded word list is sorted from most common -> least common), which roughly matches the words
+// the Solver considers likely answers.
+const ANSWER_POOL_SIZE: usize = 2700;
+
+// How many games to simulate, the answers are spread evenly over the answer pool
+const N_SIMULATED_GAMES: usize = 300;
+
+fn main() {
+    write_guess_count_model().expect("should work");
+}
+
+///
+/// Simulates games with the default Solver, and records (entropy, guesses taken to solve) at every
+/// turn of every game. Then fits a GuessCountModel to that data and writes it to the embedded data
+/// directory.
+///
+fn write_guess_count_model() -> io::Result<()> {
+    let start_at = Instant::now();
+    let answers = DATA.allowed_words[..ANSWER_POOL_SIZE.min(DATA.allowed_words.len())]
+        .iter()
+        .step_by((ANSWER_POOL_SIZE / N_SIMULATED_GAMES).max(1));
+
+    let mut samples = Vec::new();
+    let mut n_games = 0;
+    let mut n_failed = 0;
+    let mut solver = Solver::default();
+    solver.load_coloring_table();
+    for answer in answers {
+        solver.reset();
+        match simulate_game(&mut solver, answer) {
+            Some(game_samples) => samples.extend(game_samples),
+            None => n_failed += 1,
+        }
+        n_games += 1;
+    }
+
+    let model = GuessCountModel::fit(
+        samples
+            .iter()
+            .map(|&(entropy, guesses)| (entropy, guesses, 1.0)),
+    )
+    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unable to fit model"))?;
+
+    let at = format!("{}{}", EMBED_DATA_DIRECTORY, GUESS_COUNT_MODEL_FILE_NAME);
+    let mut f = fs::File::create(&at)?;
+    writeln!(f, "{}", model)?;
+
+    eprintln!(
+        "done! fitted {} from {} samples ({} games, {} unsolved) and wrote it to {} in {:.2}s",
+        model,
+        samples.len(),
+        n_games,
+        n_failed,
+        at,
+        start_at.elapsed().as_secs_f64()
+    );
+    Ok(())
+}
+
+///
+/// Plays a game against the given answer, always making the top recommended guess. Returns the
+/// entropy before each guess, paired with the number of guesses it took to solve from there. Returns
+/// None if the game was not solved.
+///
+fn simulate_game(
+    solver: &mut Solver<'static>,
+    answer: &str,
+) -> Option<Vec<(WordleFloat, WordleFloat)>> {
+    let mut entropies = Vec::new();
+    while solver.can_guess() {
+        let guess = solver.top_k_guesses::<1>().next()?.word;
+        entropies.push(solver.remaining_entropy());
+        solver
+            .make_guess(guess, Colorings::with_guess_answer(guess, answer))
+            .expect("recommended guess should be valid");
+    }
+
+    if !solver.is_solved() {
+        return None;
+    }
+
+    let n_guesses = entropies.len();
+    Some(
+        entropies
+            .into_iter()
+            .enumerate()
+            .map(|(idx, entropy)| (entropy, (n_guesses - idx) as WordleFloat))
+            .collect(),
+    )
+}
@@ -0,0 +1,290 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    time::Instant,
+};
+use wordle_site::wordle::*;
+
+// The parameters are searched on a grid of this many values (on a log scale) for each parameter,
+// which is then zoomed in on around the best value ZOOM_ROUNDS times
+const LIKELIHOOD_GRID_SIZE: usize = 24;
+const GUESSES_GRID_SIZE: usize = 5;
+const ZOOM_ROUNDS: usize = 4;
+
+// The range of widths which are searched (n_common is searched from 1 to the number of words)
+const MIN_WIDTH: WordleFloat = 0.1;
+const MAX_WIDTH: WordleFloat = 100.0;
+
+// How many games to simulate for each set of parameters when minimizing the average number of
+// guesses, the answers are spread evenly over the past answers
+const N_SIMULATED_GAMES: usize = 100;
+
+///
+/// What the fitted parameters should be best at
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Objective {
+    /// Maximize the likelihood of the past answers (in the order they were picked, since an answer
+    /// is never picked twice)
+    Likelihood,
+    /// Minimize the average number of guesses the Solver takes to find the past answers. This is
+    /// much slower, because every set of parameters needs it's own simulated games.
+    Guesses,
+}
+
+///
+/// Usage: gen_word_prior [likelihood|guesses] [past answers file]
+///
+/// Fits the parameters of the RankSigmoidPrior to the dated answers of past daily puzzles (see
+/// past_answers_file_name for the default file and it's format), and writes them to the embedded
+/// data directory for the Solver to load. When the default file doesn't exist there is nothing to
+/// fit, and the Solver keeps using the default parameters.
+///
+/// The prior depends on the rank order of the words, so this runs after gen_all_data writes the
+/// word lists (`gen_all_data words`), and before it writes the cached default state data
+/// (`gen_all_data default_state`) so that the default state data is computed with the fitted prior.
+///
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let objective = match args.next().as_deref() {
+        None | Some("likelihood") => Objective::Likelihood,
+        Some("guesses") => Objective::Guesses,
+        Some(other) => panic!("unknown objective '{}'", other),
+    };
+    let path = args.next().map(PathBuf::from);
+    write_word_prior(&GameConfig::default(), objective, path).expect("should work");
+}
+
+fn write_word_prior(
+    config: &GameConfig,
+    objective: Objective,
+    path: Option<PathBuf>,
+) -> io::Result<()> {
+    let start_at = Instant::now();
+    let at = format!("{}{}", EMBED_DATA_DIRECTORY, word_prior_file_name(config));
+    let path = match path {
+        Some(path) => path,
+        None => {
+            let path = PathBuf::from(format!(
+                "{}{}",
+                DATA_DIRECTORY,
+                past_answers_file_name(&config.alphabet())
+            ));
+            if !path.exists() {
+                eprintln!("no past answers in {}, nothing to fit", path.display());
+                // don't leave behind parameters which were fitted to answers that are gone now
+                return match fs::remove_file(&at) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+                    _ => Ok(()),
+                };
+            }
+            path
+        }
+    };
+
+    let past_answers = read_past_answers(config, &path).map_err(io::Error::other)?;
+    let data = Data::read_with_config(config).map_err(|err| match err {
+        LoadDataErr::MissingAllowedWordsFile => {
+            io::Error::other("no ordered allowed words, run `gen_all_data words` first")
+        }
+        err => io::Error::other(err),
+    })?;
+    let mut solver = Solver::with_config(*config, &data);
+
+    // answers which the solver doesn't consider possible can't tell us anything about the prior
+    let mut seen = HashSet::new();
+    let answers: Vec<WordId> = past_answers
+        .iter()
+        .filter_map(|past| solver.word_bank().id(&past.word))
+        .filter(|id| solver.remaining_possibilities().contains(*id) && seen.insert(*id))
+        .collect();
+    if answers.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "none of the past answers are possible answers",
+        ));
+    }
+
+    let n_words = solver.word_bank().len();
+    let (prior, cost) = match objective {
+        Objective::Likelihood => {
+            let words = solver.word_bank().words().to_vec();
+            let possible = solver.remaining_possibilities().clone();
+            // the solver also penalizes the inflections, so the prior is fitted on top of that
+            let penalties: Vec<WordleFloat> = words
+                .iter()
+                .map(|word| match solver.inflection(word) {
+                    Some(_) => solver.inflection_penalty(),
+                    None => 1.0,
+                })
+                .collect();
+            search(n_words, LIKELIHOOD_GRID_SIZE, |prior| {
+                let weights: Vec<WordleFloat> = prior
+                    .weights(&words)
+                    .iter()
+                    .zip(&penalties)
+                    .map(|(weight, penalty)| weight * penalty)
+                    .collect();
+                -log_likelihood(&weights, &possible, &answers) / answers.len() as WordleFloat
+            })
+        }
+        Objective::Guesses => {
+            solver.load_coloring_table();
+            let sample: Vec<WordId> = answers
+                .iter()
+                .copied()
+                .step_by((answers.len() / N_SIMULATED_GAMES).max(1))
+                .collect();
+            search(n_words, GUESSES_GRID_SIZE, |prior| {
+                solver.set_word_prior(Box::new(prior));
+                average_guesses(&mut solver, &sample)
+            })
+        }
+    };
+
+    let mut f = fs::File::create(&at)?;
+    writeln!(f, "{} {}", prior.n_common, prior.width)?;
+
+    let cost_name = match objective {
+        Objective::Likelihood => "bits per answer",
+        Objective::Guesses => "guesses per game",
+    };
+    eprintln!(
+        "done! fitted n_common={:.1} width={:.3} ({:.3} {}) from {} past answers and wrote it to {} in {:.2}s",
+        prior.n_common,
+        prior.width,
+        cost,
+        cost_name,
+        answers.len(),
+        at,
+        start_at.elapsed().as_secs_f64()
+    );
+    Ok(())
+}
+
+///
+/// Finds the parameters with the lowest cost. A grid of grid_size x grid_size parameters is
+/// evaluated (spaced evenly on a log scale), and then the search zooms in on the best one.
+///
+fn search(
+    n_words: usize,
+    grid_size: usize,
+    mut cost: impl FnMut(RankSigmoidPrior) -> WordleFloat,
+) -> (RankSigmoidPrior, WordleFloat) {
+    let bounds = [
+        (0.0, (n_words as WordleFloat).ln()),
+        (MIN_WIDTH.ln(), MAX_WIDTH.ln()),
+    ];
+    let mut ranges = bounds;
+    let mut best = (RankSigmoidPrior::default(), WordleFloat::INFINITY);
+    for _ in 0..ZOOM_ROUNDS {
+        let steps = ranges.map(|(lo, hi)| (hi - lo) / (grid_size - 1) as WordleFloat);
+        let mut best_ln = [0.0; 2];
+        for i in 0..grid_size {
+            for j in 0..grid_size {
+                let ln = [
+                    ranges[0].0 + steps[0] * i as WordleFloat,
+                    ranges[1].0 + steps[1] * j as WordleFloat,
+                ];
+                let prior = RankSigmoidPrior {
+                    n_common: ln[0].exp(),
+                    width: ln[1].exp(),
+                };
+                let c = cost(prior);
+                if c < best.1 {
+                    best = (prior, c);
+                    best_ln = ln;
+                }
+            }
+        }
+
+        // the next round searches between the neighbours of the best parameters
+        for (idx, range) in ranges.iter_mut().enumerate() {
+            *range = (
+                (best_ln[idx] - steps[idx]).max(bounds[idx].0),
+                (best_ln[idx] + steps[idx]).min(bounds[idx].1),
+            );
+        }
+    }
+
+    best
+}
+
+///
+/// The log2 likelihood of the answers being picked in order (from the possible words, with the
+/// given weights), where a word can't be picked again once it was the answer
+///
+fn log_likelihood(weights: &[WordleFloat], possible: &WordSet, answers: &[WordId]) -> WordleFloat {
+    let mut total: WordleFloat = possible.iter().map(|id| weights[id as usize]).sum();
+    let mut out = 0.0;
+    for id in answers {
+        let weight = weights[*id as usize];
+        out += (weight / total).log2();
+        total -= weight;
+    }
+    out
+}
+
+///
+/// Plays a game against each answer (always making the top recommended guess), and returns the
+/// average number of guesses taken. Unsolved games count as one more guess than the turn limit.
+///
+fn average_guesses(solver: &mut Solver, answers: &[WordId]) -> WordleFloat {
+    // the first guess only depends on the prior, so it's computed once for every game
+    solver.reset();
+    let first_guess = solver
+        .top_k_guesses::<1>()
+        .next()
+        .expect("should have a first guess")
+        .word;
+
+    let unsolved = solver.config().max_turns().unwrap_or(NUM_TURNS) + 1;
+    let total: usize = answers
+        .iter()
+        .map(|id| {
+            let answer = solver.word_bank().word(*id);
+            solver.reset();
+            let mut guess = first_guess;
+            loop {
+                solver
+                    .make_guess(guess, Colorings::with_guess_answer(guess, answer))
+                    .expect("recommended guess should be valid");
+                if solver.is_solved() {
+                    return solver.num_guesses();
+                }
+                match solver.top_k_guesses::<1>().next() {
+                    Some(next) if solver.can_guess() => guess = next.word,
+                    _ => return unsolved,
+                }
+            }
+        })
+        .sum();
+
+    total as WordleFloat / answers.len() as WordleFloat
+}
@@ -22,6 +22,7 @@
  * SOFTWARE.
  */
 
+mod parallel;
 mod top_k;
 
-pub use top_k::*;
+pub use {parallel::*, top_k::*};
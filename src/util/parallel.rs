@@ -0,0 +1,99 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+///
+/// Splits the items into contiguous chunks, calls f on each chunk and returns the outputs in the
+/// same order as the chunks. Chunks have at least min_chunk_size items, because it is not worth
+/// starting a thread for a tiny amount of work (use a small size when each item is expensive).
+///
+/// With the "parallel" feature enabled (on native targets) each chunk is processed on it's own
+/// thread. Otherwise (and always in the web app) all of the items are passed to f as a single
+/// chunk. Either way, concatenating the outputs gives the same result.
+///
+pub fn map_chunks<I, T, F>(items: &[I], min_chunk_size: usize, f: F) -> Vec<T>
+where
+    I: Sync,
+    T: Send,
+    F: Fn(&[I]) -> T + Sync,
+{
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    {
+        let n_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let chunk_size = items.len().div_ceil(n_threads).max(min_chunk_size.max(1));
+        if items.len() > chunk_size {
+            let f = &f;
+            return std::thread::scope(|scope| {
+                let handles: Vec<_> = items
+                    .chunks(chunk_size)
+                    .map(|chunk| scope.spawn(move || f(chunk)))
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("worker thread should not panic"))
+                    .collect()
+            });
+        }
+    }
+
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    let _ = min_chunk_size;
+
+    vec![f(items)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::TopKExt;
+
+    #[test]
+    fn test_map_chunks_keeps_order() {
+        let items: Vec<usize> = (0..1000).collect();
+        let out: Vec<usize> = map_chunks(&items, 1, |chunk| chunk.to_vec()).concat();
+        assert_eq!(out, items);
+    }
+
+    #[test]
+    fn test_merged_top_k_matches_sequential() {
+        // lots of ties, which must be broken the same way (first seen wins)
+        let items: Vec<(usize, u32)> = (0..1000).map(|i| (i, (i % 7) as u32)).collect();
+        let sequential: Vec<_> = items
+            .iter()
+            .copied()
+            .top_k::<_, _, 10>(|item| item.1)
+            .collect();
+        let merged: Vec<_> = map_chunks(&items, 64, |chunk| {
+            chunk.iter().copied().top_k::<_, _, 10>(|item| item.1)
+        })
+        .into_iter()
+        .flatten()
+        .top_k::<_, _, 10>(|item| item.1)
+        .collect();
+
+        assert_eq!(merged, sequential);
+    }
+}
@@ -22,16 +22,29 @@
  * SOFTWARE.
  */
 
-use super::global_key_hook::*;
+use super::{
+    global_key_hook::*,
+    multi_app::{MultiApp, MultiAppProps, MULTI_BOARD_GAMES},
+};
 use crate::wordle::*;
 use std::borrow::Borrow;
 use yew::prelude::*;
 
+/// How many possible fixes to suggest when the entered colorings contradict each other
+const N_DIAGNOSES: usize = 3;
+
+/// The lie models which can be picked in the settings, in the order they are cycled through
+const LIE_MODELS: [LieModel; 3] = [LieModel::Honest, LieModel::Exactly(1), LieModel::AtMost(1)];
+
 pub struct App {
     solver: StaticSolver,
     recommendations: Vec<ScoredCandidate<'static>>,
-    filled_guess: [Option<char>; WORD_SIZE],
-    filled_colors: [Coloring; WORD_SIZE],
+    diagnoses: Vec<Diagnosis<'static>>,
+    likely_lies: Vec<LikelyLie>,
+    filled_guess: Vec<Option<char>>,
+    filled_colors: Colorings,
+    /// How many boards are being solved at once (1 = regular wordle, otherwise MultiApp is shown)
+    num_boards: usize,
 
     #[allow(dead_code)]
     keydown_listener: KeyListener,
@@ -41,8 +54,20 @@ pub struct App {
 pub enum Msg {
     PickRecommendation(String),
     UpdateColoring(usize),
+    EditGuessColoring(usize, usize),
     MakeGuess,
+    UndoGuess,
+    ApplyDiagnosis(usize),
     ClearGuess,
+    ToggleGuessPool,
+    ToggleAnswerPool,
+    ToggleHardMode,
+    ToggleHostModel,
+    CycleLieModel,
+    CycleRepeatPolicy,
+    ToggleInflectionPenalty,
+    CycleScoringStrategy,
+    CycleGame,
     OnKeyDown(KeyEvent),
 }
 
@@ -51,11 +76,16 @@ impl Component for App {
     type Properties = ();
 
     fn create(ctx: &Context<Self>) -> Self {
+        let solver = Solver::default();
+        let word_size = solver.config().word_size();
         let mut out = Self {
-            solver: Solver::default(),
+            solver,
             recommendations: Vec::default(),
-            filled_guess: [None; WORD_SIZE],
-            filled_colors: [Coloring::Excluded; WORD_SIZE],
+            diagnoses: Vec::default(),
+            likely_lies: Vec::default(),
+            filled_guess: vec![None; word_size],
+            filled_colors: Colorings::new(word_size),
+            num_boards: 1,
             keydown_listener: KeyListener::create(ctx.link().callback(Msg::OnKeyDown))
                 .expect("should be able to attach key listener"),
         };
@@ -69,22 +99,22 @@ impl Component for App {
         match msg {
             PickRecommendation(recommendation) => {
                 self.accept_suggestion(recommendation.as_str());
+                self.fill_adversary_coloring();
                 true
             }
             UpdateColoring(idx) => {
                 if self.solver.can_guess() {
                     let src = &mut self.filled_colors[idx];
-                    *src = match *src {
-                        Coloring::Excluded => Coloring::Misplaced,
-                        Coloring::Misplaced => Coloring::Correct,
-                        Coloring::Correct => Coloring::Excluded,
-                    };
+                    *src = next_coloring(*src);
                     true
                 } else {
                     false
                 }
             }
+            EditGuessColoring(guess_idx, idx) => self.edit_guess_coloring(guess_idx, idx),
             MakeGuess => self.make_guess(),
+            UndoGuess => self.undo_guess(),
+            ApplyDiagnosis(idx) => self.apply_diagnosis(idx),
             ClearGuess => {
                 if self.enable_reset_button() {
                     let reset_entire_game = !self.has_any_guess_state();
@@ -98,6 +128,86 @@ impl Component for App {
                     false
                 }
             }
+            ToggleGuessPool => {
+                self.solver.set_guess_pool(match self.solver.guess_pool() {
+                    GuessPool::RemainingPossibilities => GuessPool::AllowedWords,
+                    GuessPool::AllowedWords => GuessPool::RemainingPossibilities,
+                });
+                self.update_recommendations();
+                true
+            }
+            ToggleAnswerPool => {
+                self.solver
+                    .set_answer_pool(match self.solver.answer_pool() {
+                        AnswerPool::AnswerList => AnswerPool::AllowedWords,
+                        AnswerPool::AllowedWords => AnswerPool::AnswerList,
+                    });
+                self.update_recommendations();
+                true
+            }
+            ToggleHardMode => {
+                self.solver.set_hard_mode(!self.solver.is_hard_mode());
+                self.update_recommendations();
+                true
+            }
+            ToggleHostModel => {
+                self.solver.set_host_model(match self.solver.host_model() {
+                    HostModel::Fair => HostModel::Adversarial,
+                    HostModel::Adversarial => HostModel::Fair,
+                });
+                self.update_recommendations();
+                self.fill_adversary_coloring();
+                true
+            }
+            CycleLieModel => {
+                let current = self.solver.lie_model();
+                let next_idx = LIE_MODELS
+                    .iter()
+                    .position(|model| *model == current)
+                    .map(|idx| (idx + 1) % LIE_MODELS.len())
+                    .unwrap_or(0);
+                self.solver.set_lie_model(LIE_MODELS[next_idx]);
+                self.update_recommendations();
+                true
+            }
+            CycleRepeatPolicy => {
+                if let Some(cutoff) = self.solver.latest_past_answer_date() {
+                    self.solver
+                        .set_repeat_policy(match self.solver.repeat_policy() {
+                            RepeatPolicy::Allow => RepeatPolicy::Exclude { cutoff },
+                            RepeatPolicy::Exclude { .. } => RepeatPolicy::Discount {
+                                cutoff,
+                                factor: DEFAULT_REPEAT_DISCOUNT,
+                            },
+                            RepeatPolicy::Discount { .. } => RepeatPolicy::Allow,
+                        });
+                    self.update_recommendations();
+                }
+                true
+            }
+            ToggleInflectionPenalty => {
+                self.solver
+                    .set_inflection_penalty(if self.solver.inflection_penalty() < 1.0 {
+                        1.0
+                    } else {
+                        DEFAULT_INFLECTION_PENALTY
+                    });
+                self.update_recommendations();
+                true
+            }
+            CycleScoringStrategy => {
+                self.cycle_scoring_strategy();
+                self.update_recommendations();
+                true
+            }
+            CycleGame => {
+                self.num_boards = MULTI_BOARD_GAMES
+                    .iter()
+                    .map(|(num_boards, _)| *num_boards)
+                    .find(|num_boards| *num_boards > self.num_boards)
+                    .unwrap_or(1);
+                true
+            }
             OnKeyDown(mut event) => self.handle_keydown(&mut event),
         }
     }
@@ -106,8 +216,12 @@ impl Component for App {
         html! {
             <div class="viewport">
                 <div class="body">
-                    {self.show_game(ctx)}
-                    {self.show_recommendation_html(ctx)}
+                    if self.num_boards > 1 {
+                        {self.show_multi_app(ctx)}
+                    } else {
+                        {self.show_game(ctx)}
+                        {self.show_recommendation_html(ctx)}
+                    }
                 </div>
                 { Self::show_footer_safe() }
             </div>
@@ -120,6 +234,54 @@ impl App {
         self.recommendations.clear();
         self.recommendations
             .extend(self.solver.top_k_guesses::<{ N_RECOMMENDATIONS }>());
+
+        // when the colorings contradict each other, it's most likely that a square was colored
+        // incorrectly, so we try to figure out which one
+        self.diagnoses = if self.solver.num_guesses() > 0 && !self.solver.has_possible_guesses() {
+            self.solver.diagnose_contradiction::<{ N_DIAGNOSES }>()
+        } else {
+            Vec::new()
+        };
+        self.likely_lies = self.solver.likely_lies();
+    }
+
+    fn apply_diagnosis(&mut self, idx: usize) -> bool {
+        let diagnosis = match self.diagnoses.get(idx) {
+            Some(diagnosis) => diagnosis.clone(),
+            None => return false,
+        };
+
+        if let Err(err) = self.solver.apply_diagnosis(&diagnosis) {
+            log::warn!("could not apply diagnosis {:?} {:?}", diagnosis, err);
+            return false;
+        }
+
+        self.clear_guess();
+        self.update_recommendations();
+        self.pre_fill_answer();
+        true
+    }
+
+    fn cycle_scoring_strategy(&mut self) {
+        let strategies = builtin_scoring_strategies();
+        let current_id = self.solver.scoring_strategy().id();
+        let next_idx = strategies
+            .iter()
+            .position(|strategy| strategy.id() == current_id)
+            .map(|idx| (idx + 1) % strategies.len())
+            .unwrap_or(0);
+
+        if let Some(next) = strategies.into_iter().nth(next_idx) {
+            self.solver.set_scoring_strategy(next);
+        }
+    }
+
+    fn show_multi_app(&self, ctx: &Context<Self>) -> Html {
+        let props = MultiAppProps {
+            num_boards: self.num_boards,
+            on_cycle_game: ctx.link().callback(|_| Msg::CycleGame),
+        };
+        html! { <MultiApp ..props /> }
     }
 
     fn show_info_html() -> Html {
@@ -138,6 +300,10 @@ impl App {
                         <li>
                             {"Hit the ✔️ button once the colors match those provided by Wordle"}
                         </li>
+                        <li>{
+                            "Made a mistake? Click on the squares of an earlier guess to fix its \
+                            colors, or hit the ↩️ button to undo the last guess"
+                        }</li>
                     </ol>
                     {"Suggestions will be updated after you make each guess, until the puzzle \
                     is solved."}
@@ -188,8 +354,9 @@ impl App {
                 <div class="title">{format!("Suggestions ({})", self.num_suggestions())}</div>
                 if self.solver.can_guess() {
                     { self.show_recommendation_details() }
+                    { self.show_settings(ctx) }
                     {
-                        if let Some(top_guess) = self.recommendations.get(0) {
+                        if let Some(top_guess) = self.recommendations.first() {
                             Self::show_recommendation_instructions(ctx, top_guess.word)
                         } else {
                             html! {<></> }
@@ -202,6 +369,7 @@ impl App {
     }
 
     fn show_recommendation_details(&self) -> Html {
+        let constraints = self.solver.constraints();
         html! {
             <div class="detail">
                 <div class="possibilities">{ self.possibilities_remaining_msg() }</div>
@@ -211,6 +379,104 @@ impl App {
                         self.solver.remaining_entropy(),
                     )}
                 </div>
+                if let Some(expected_guesses) = self.solver.expected_guesses_to_solve() {
+                    <div class="expected-guesses">
+                        {format!("Expect to solve in {:.02} more guesses", expected_guesses)}
+                    </div>
+                }
+                if !self.likely_lies.is_empty() {
+                    <div class="lies">
+                        {
+                            self.likely_lies
+                                .iter()
+                                .map(|lie| html! { <div>{lie.to_string()}</div> })
+                                .collect::<Html>()
+                        }
+                    </div>
+                } else if !constraints.is_empty() {
+                    <div class="constraints">
+                        {
+                            constraints
+                                .summary()
+                                .into_iter()
+                                .map(|line| html! { <div>{line}</div> })
+                                .collect::<Html>()
+                        }
+                    </div>
+                }
+            </div>
+        }
+    }
+
+    fn show_settings(&self, ctx: &Context<Self>) -> Html {
+        let allow_probes = self.solver.guess_pool() == GuessPool::AllowedWords;
+        html! {
+            <div class="settings">
+                { Self::show_toggle(
+                    ctx,
+                    allow_probes,
+                    "suggest words that can't be the answer",
+                    Msg::ToggleGuessPool) }
+                if self.solver.has_answer_list() {
+                    { Self::show_toggle(
+                        ctx,
+                        self.solver.answer_pool() == AnswerPool::AllowedWords,
+                        "any allowed word can be the answer",
+                        Msg::ToggleAnswerPool) }
+                }
+                if self.solver.has_inflections() {
+                    { Self::show_toggle(
+                        ctx,
+                        self.solver.inflection_penalty() < 1.0,
+                        "plurals & past tenses are less likely",
+                        Msg::ToggleInflectionPenalty) }
+                }
+                { Self::show_toggle(
+                    ctx,
+                    self.solver.is_hard_mode(),
+                    "hard mode",
+                    Msg::ToggleHardMode) }
+                { Self::show_toggle(
+                    ctx,
+                    self.solver.host_model() == HostModel::Adversarial,
+                    "absurdle (the answer dodges your guesses)",
+                    Msg::ToggleHostModel) }
+                <div
+                    class="click-text toggle"
+                    onclick={ ctx.link().callback(|_| Msg::CycleScoringStrategy) }>
+                    { format!("↻ ranking by {}", self.solver.scoring_strategy().name()) }
+                </div>
+                <div
+                    class="click-text toggle"
+                    onclick={ ctx.link().callback(|_| Msg::CycleLieModel) }>
+                    { format!("↻ colors have {}", self.solver.lie_model()) }
+                </div>
+                if self.solver.latest_past_answer_date().is_some() {
+                    <div
+                        class="click-text toggle"
+                        onclick={ ctx.link().callback(|_| Msg::CycleRepeatPolicy) }>
+                        { format!("↻ {}", self.solver.repeat_policy()) }
+                    </div>
+                }
+                <div
+                    class="click-text toggle"
+                    onclick={ ctx.link().callback(|_| Msg::CycleGame) }>
+                    {"↻ playing Wordle"}
+                </div>
+                if allow_probes {
+                    <div class="legend">{"★ = could be the answer"}</div>
+                }
+            </div>
+        }
+    }
+
+    fn show_toggle(ctx: &Context<Self>, on: bool, label: &'static str, msg: Msg) -> Html {
+        html! {
+            <div
+                class="click-text toggle"
+                onclick={ ctx.link().callback(move |_| msg.clone()) }>
+                { if on { "☑ " } else { "☐ " } }
+                { label }
             </div>
         }
     }
@@ -256,7 +522,9 @@ impl App {
         let empty_list = !self.solver.can_guess() || self.recommendations.is_empty();
         html! {
             <div class="list">
-                if empty_list {
+                if !self.diagnoses.is_empty() {
+                    { self.show_diagnoses(ctx) }
+                } else if empty_list {
                     <div class="empty-msg">
                         <>{"This game is complete, press the X button to reset, or "}</>
                         <span
@@ -280,6 +548,27 @@ impl App {
         }
     }
 
+    fn show_diagnoses(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="empty-msg diagnoses">
+                <>{"No words match these colors. Did you mean..."}</>
+                {
+                    self.diagnoses
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, diagnosis)| html! {
+                            <div
+                                class="click-text diagnosis"
+                                onclick={ ctx.link().callback(move |_| Msg::ApplyDiagnosis(idx)) }>
+                                {format!("{}? ({})", diagnosis, diagnosis.answer)}
+                            </div>
+                        })
+                        .collect::<Html>()
+                }
+            </div>
+        }
+    }
+
     fn show_recommendation_item(
         idx: usize,
         item: &ScoredCandidate<'static>,
@@ -287,16 +576,31 @@ impl App {
     ) -> Html {
         html! {
             <div
-                class="item"
+                class={classes!(
+                    "item",
+                    if item.is_possible_answer { "possible" } else { "probe" }
+                )}
                 onclick={ ctx
                     .link()
                     .callback(|_| Msg::PickRecommendation(item.word.to_string()))}>
                 <div class="ordinal">{format!("#{:02}", idx + 1)}</div>
                 <div class="word">{&item.word}</div>
+                <div class="possible-marker">
+                    { if item.is_possible_answer { "★" } else { "" } }
+                </div>
                 <div class="details">
-                    <span class="score">{format!("{:.2}", item.score.abs)}</span>
+                    if let Some(expected_guesses) = item.score.expected_guesses {
+                        <span class="expected-guesses">
+                            {format!("{:.2} guesses", expected_guesses)}
+                        </span>
+                    } else {
+                        <span class="score">{format!("{:.2}", item.score.abs)}</span>
+                    }
                     <span class="expected-info">{format!("{:.2}", item.score.expected_info)}</span>
                     <span class="weight">{format!("{:.4}", item.score.weight)}</span>
+                    <span class="worst-case" title="most possible words left after this guess">
+                        {format!("≤{}", item.score.worst_case)}
+                    </span>
                 </div>
             </div>
         }
@@ -317,7 +621,7 @@ impl App {
                 </p>
                 <div class="game">
                     {
-                        (0..NUM_TURNS)
+                        (0..self.num_rows())
                             .map(|idx| self.show_wordle_row(ctx, &guesses, idx))
                             .collect::<Html>()
                     }
@@ -337,26 +641,52 @@ impl App {
         }
     }
 
+    /// The number of rows in the game, which is one per turn (or when there's no limit on the number
+    /// of turns, enough rows for the guesses made so far & the next guess)
+    fn num_rows(&self) -> usize {
+        let num_guesses = self.solver.num_guesses();
+        match self.solver.config().max_turns() {
+            Some(max_turns) => max_turns,
+            None if self.solver.can_guess() || num_guesses == 0 => num_guesses + 1,
+            None => num_guesses,
+        }
+    }
+
     fn show_wordle_guessed_row(&self, ctx: &Context<Self>, guess: &Guess, index: usize) -> Html {
-        let show_reset = index == NUM_TURNS - 1;
+        let show_reset = index == self.num_rows() - 1;
         html! {
-            <div class="game-row filled inactive">
+            <div class="game-row filled inactive editable">
                 {
-                    (0..WORD_SIZE).zip(guess.word.iter().copied()).map(|(idx, chr)| html! {
-                        <div class={classes!(
-                            "game-cell",
-                            "filled",
-                            match guess.coloring[idx] {
-                                Coloring::Excluded => "c-excluded",
-                                Coloring::Misplaced => "c-misplaced",
-                                Coloring::Correct => "c-correct",
-                            }
-                        )}>{chr as char}</div>
+                    guess.word.letters().iter().copied().enumerate().map(|(idx, chr)| {
+                        let lie = self
+                            .likely_lies
+                            .iter()
+                            .find(|lie| lie.tile.guess_idx == index && lie.tile.idx == idx);
+                        html! {
+                            <div
+                                class={classes!(
+                                    "game-cell",
+                                    "filled",
+                                    match guess.coloring[idx] {
+                                        Coloring::Excluded => "c-excluded",
+                                        Coloring::Misplaced => "c-misplaced",
+                                        Coloring::Correct => "c-correct",
+                                    },
+                                    lie.map(|_| "likely-lie")
+                                )}
+                                title={lie.map(|lie| lie.to_string())}
+                                onclick={ctx
+                                    .link()
+                                    .callback(move |_| Msg::EditGuessColoring(index, idx))}>
+                                {chr}
+                            </div>
+                        }
                     }).collect::<Html>()
                 }
                 if show_reset {
                     <div class="buttons">
                         {self.show_reset_button(ctx)}
+                        {self.show_undo_button(ctx)}
                     </div>
                 }
                 <div class="entropy">
@@ -372,6 +702,7 @@ impl App {
     fn show_wordle_active_row(&self, ctx: &Context<Self>) -> Html {
         let active_idx = self.next_chr_idx();
         let can_play = self.solver.can_guess();
+        let impossible_tiles = self.impossible_tiles();
         log::debug!("active_idx = {:?}", &active_idx);
         html! {
             <div class={classes!("game-row", if can_play { "active" } else { "empty inactive" })}>
@@ -379,7 +710,7 @@ impl App {
                     self.filled_guess
                         .iter()
                         .copied()
-                        .zip(self.filled_colors.iter())
+                        .zip(self.filled_colors.as_slice().iter())
                         .enumerate()
                         .map(|(idx, (chr, coloring))| html! {
                             <div class={classes!(
@@ -393,7 +724,8 @@ impl App {
                                     Coloring::Excluded => "c-excluded",
                                     Coloring::Misplaced => "c-misplaced",
                                     Coloring::Correct => "c-correct",
-                                })}
+                                },
+                                impossible_tiles[idx].then_some("impossible"))}
                                 onclick={ctx.link().callback(move |_| Msg::UpdateColoring(idx))}>
                                 { chr.unwrap_or(' ') }
                             </div>
@@ -404,7 +736,11 @@ impl App {
                     if can_play {
                         {self.show_confirm_button(ctx)}
                     }
+                    {self.show_undo_button(ctx)}
                 </div>
+                if let Some(problem) = self.guess_problem().filter(|_| can_play) {
+                    <div class="problem">{problem}</div>
+                }
             </div>
         }
     }
@@ -445,11 +781,21 @@ impl App {
         )
     }
 
+    fn show_undo_button(&self, ctx: &Context<Self>) -> Html {
+        Self::wordle_button(
+            ctx,
+            "undo-button",
+            "↩️",
+            self.solver.num_guesses() > 0,
+            Msg::UndoGuess,
+        )
+    }
+
     fn show_wordle_empty_row(&self) -> Html {
         html! {
             <div class="game-row empty inactive">
                 {
-                    (0..WORD_SIZE).map(|_| html! {
+                    (0..self.solver.config().word_size()).map(|_| html! {
                         <div class="game-cell empty inactive"></div>
                     }).collect::<Html>()
                 }
@@ -470,9 +816,8 @@ impl App {
     fn accept_suggestion(&mut self, suggestion: &str) {
         assert!(is_wordle_str(suggestion));
 
-        let bs = suggestion.as_bytes();
-        for (src, target) in bs.iter().copied().zip(self.filled_guess.iter_mut()) {
-            *target = Some(src as char);
+        for (src, target) in suggestion.chars().zip(self.filled_guess.iter_mut()) {
+            *target = Some(src);
         }
     }
 
@@ -487,12 +832,11 @@ impl App {
             return false;
         };
 
-        if !self.solver.is_guess_permitted(&guess_str) {
+        if self.guess_problem().is_some() {
             return false;
         }
 
-        let colorings = Colorings(self.filled_colors);
-        if let Err(err) = self.solver.make_guess(&guess_str, colorings) {
+        if let Err(err) = self.solver.make_guess(&guess_str, self.filled_colors) {
             log::warn!("weird error when guessing {:?} {:?}", guess_str, err);
         }
 
@@ -502,23 +846,71 @@ impl App {
         true
     }
 
-    fn guess_str(&self) -> Option<String> {
-        let mut guess = [0; WORD_SIZE];
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..WORD_SIZE {
-            if let Some(c) = self.filled_guess[i] {
-                guess[i] = c as u8;
-            } else {
-                return None;
+    /// Removes the last guess & puts it back into the active row, so that it can be fixed
+    fn undo_guess(&mut self) -> bool {
+        let undone = match self.solver.undo_last_guess() {
+            Some(guess) => guess,
+            None => return false,
+        };
+
+        for i in 0..undone.word.len() {
+            self.filled_guess[i] = Some(undone.word[i]);
+            self.filled_colors[i] = undone.coloring[i];
+        }
+
+        self.update_recommendations();
+        true
+    }
+
+    /// Changes the coloring of a single tile of a guess which was already made
+    fn edit_guess_coloring(&mut self, guess_idx: usize, idx: usize) -> bool {
+        let guess = match self.solver.iter_guesses().nth(guess_idx) {
+            Some(guess) => *guess,
+            None => return false,
+        };
+
+        // some colorings can't be applied (for example, an all green guess followed by more
+        // guesses), so we skip past those until we find one that works
+        let word = guess.word.to_string();
+        let mut coloring = guess.coloring;
+        loop {
+            coloring[idx] = next_coloring(coloring[idx]);
+            if coloring == guess.coloring {
+                return false;
+            }
+
+            match self.solver.replace_guess(guess_idx, &word, coloring) {
+                Ok(()) => break,
+                Err(err) => log::debug!("cannot recolor {} as {}: {}", word, coloring, err),
             }
         }
 
-        Some(String::from_utf8_lossy(&guess).to_string())
+        self.update_recommendations();
+        true
+    }
+
+    /// In absurdle mode, colors the (completely filled) guess the way absurdle is going to color it
+    fn fill_adversary_coloring(&mut self) {
+        if self.solver.host_model() != HostModel::Adversarial {
+            return;
+        }
+
+        let predicted = self
+            .guess_str()
+            .and_then(|guess| self.solver.predict_adversary_coloring(&guess));
+        if let Some(coloring) = predicted {
+            self.filled_colors = coloring;
+        }
+    }
+
+    fn guess_str(&self) -> Option<String> {
+        self.filled_guess.iter().copied().collect()
     }
 
     fn clear_guess(&mut self) {
-        self.filled_guess = [None; WORD_SIZE];
-        self.filled_colors = [Coloring::Excluded; WORD_SIZE];
+        let word_size = self.solver.config().word_size();
+        self.filled_guess = vec![None; word_size];
+        self.filled_colors = Colorings::new(word_size);
     }
 
     fn pre_fill_answer(&mut self) {
@@ -526,22 +918,24 @@ impl App {
             return;
         }
 
-        // if there's literally just one recommendation, then it's the right answer
-        if let Some(only) = self.recommendations.get(0) {
-            if self.recommendations.len() == 1 {
-                let only_word = &*only.word;
-                self.accept_suggestion(only_word);
-                self.filled_colors = [Coloring::Correct; WORD_SIZE];
+        // if there's literally just one possibility, then it's the right answer (it might not be
+        // recommended, when other words are considered too)
+        if self.solver.num_remaining_possibilities() == 1 {
+            let only_id = self.solver.remaining_possibilities().iter().next();
+            if let Some(only_id) = only_id {
+                let only_word = self.solver.word_bank().word(only_id).to_string();
+                self.accept_suggestion(&only_word);
+                self.filled_colors = Colorings::all_correct(num_letters(&only_word));
                 return;
             }
         }
 
         // copy forward green squares from the previous guess, if there are any guesses
         if let Some(prev_guess) = self.solver.iter_guesses().last() {
-            for i in 0..WORD_SIZE {
-                let coloring = prev_guess.coloring.0[i];
+            for i in 0..prev_guess.word.len() {
+                let coloring = prev_guess.coloring[i];
                 if coloring == Coloring::Correct {
-                    self.filled_guess[i] = Some(prev_guess.word[i] as char);
+                    self.filled_guess[i] = Some(prev_guess.word[i]);
                     self.filled_colors[i] = coloring;
                 }
             }
@@ -553,10 +947,35 @@ impl App {
     }
 
     fn enable_confirm_button(&self) -> bool {
-        if let Some(g) = self.guess_str() {
-            self.solver.is_guess_permitted(g.borrow())
-        } else {
-            false
+        self.guess_str().is_some() && self.guess_problem().is_none()
+    }
+
+    /// Explains why the (completely filled) guess cannot be submitted, if it can't be
+    fn guess_problem(&self) -> Option<String> {
+        let guess = self.guess_str()?;
+        if !self.solver.is_guess_permitted(guess.borrow()) {
+            return Some("not in word list".to_string());
+        }
+
+        // a lie can make the colors look impossible
+        if self.solver.lie_model().is_honest()
+            && !self.filled_colors.is_consistent_with_guess(guess.borrow())
+        {
+            return Some("wordle can't show these colors".to_string());
+        }
+
+        self.solver
+            .hard_mode_violation(guess.borrow())
+            .map(|violation| violation.to_string())
+    }
+
+    /// Flags the squares of the (completely filled) guess which make its coloring impossible
+    fn impossible_tiles(&self) -> Vec<bool> {
+        match self.guess_str() {
+            Some(guess) if self.solver.lie_model().is_honest() => {
+                self.filled_colors.inconsistent_tiles(guess.borrow())
+            }
+            _ => vec![false; self.filled_guess.len()],
         }
     }
 
@@ -565,7 +984,10 @@ impl App {
     }
 
     fn has_any_coloring_state(&self) -> bool {
-        self.filled_colors.iter().any(|c| c != &Coloring::Excluded)
+        self.filled_colors
+            .as_slice()
+            .iter()
+            .any(|c| c != &Coloring::Excluded)
     }
 
     fn show_footer_safe() -> Html {
@@ -603,7 +1025,8 @@ impl App {
     }
 
     fn handle_keydown(&mut self, event: &mut KeyEvent) -> bool {
-        if event.is_control_key() {
+        // MultiApp has its own key listener while it is shown
+        if event.is_control_key() || self.num_boards > 1 {
             return false;
         }
 
@@ -614,7 +1037,7 @@ impl App {
             // all letter keys are of the form "KeyA" or "KeyB" etc
             code if code.starts_with("Key") && code.len() == 4 => {
                 let l = code.as_bytes()[3] as char;
-                if ('A'..='Z').contains(&l) {
+                if l.is_ascii_uppercase() {
                     self.handle_letter_entered(event, l.to_ascii_lowercase())
                 } else {
                     false
@@ -643,12 +1066,15 @@ impl App {
                 // then we can automatically label this as green
                 if let Some(previous) = self.solver.iter_guesses().last() {
                     let p_coloring = previous.coloring[idx];
-                    let p_letter = previous.word[idx] as char;
+                    let p_letter = previous.word[idx];
 
                     if p_coloring == Coloring::Correct && p_letter == letter {
                         self.filled_colors[idx] = Coloring::Correct;
                     }
                 }
+
+                // absurdle picks the coloring based on the guess, so we already know what it is
+                self.fill_adversary_coloring();
                 event.prevent_default();
                 true
             }
@@ -703,3 +1129,12 @@ impl App {
         self.update_recommendations();
     }
 }
+
+/// The coloring a tile changes to when it is clicked
+pub(super) fn next_coloring(coloring: Coloring) -> Coloring {
+    match coloring {
+        Coloring::Excluded => Coloring::Misplaced,
+        Coloring::Misplaced => Coloring::Correct,
+        Coloring::Correct => Coloring::Excluded,
+    }
+}
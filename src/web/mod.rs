@@ -24,5 +24,6 @@
 
 mod app;
 mod global_key_hook;
+mod multi_app;
 
 pub use app::App;
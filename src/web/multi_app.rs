@@ -0,0 +1,495 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{app::next_coloring, global_key_hook::*};
+use crate::wordle::*;
+use yew::prelude::*;
+
+///
+/// The games which solve several boards at once, by the number of boards
+///
+pub const MULTI_BOARD_GAMES: [(usize, &str); 3] = [(2, "Dordle"), (4, "Quordle"), (8, "Octordle")];
+
+#[derive(Properties, PartialEq)]
+pub struct MultiAppProps {
+    /// The number of boards being solved at once
+    pub num_boards: usize,
+    /// Called when the user wants to switch to the next game (with a different number of boards)
+    pub on_cycle_game: Callback<()>,
+}
+
+///
+/// The multi board version of App (for Dordle, Quordle & Octordle). There is a grid per board,
+/// but only one row to type guesses into, which is shared by every board. The colors for the
+/// guess are entered on each (unsolved) board separately.
+///
+pub struct MultiApp {
+    solver: MultiSolver<'static>,
+    recommendations: Vec<ScoredCandidate<'static>>,
+    filled_guess: Vec<Option<char>>,
+    /// The colors entered for the guess on each board
+    filled_colors: Vec<Colorings>,
+
+    #[allow(dead_code)]
+    keydown_listener: KeyListener,
+}
+
+#[derive(Debug, Clone)]
+pub enum Msg {
+    PickRecommendation(String),
+    UpdateColoring(usize, usize),
+    MakeGuess,
+    UndoGuess,
+    ClearGuess,
+    ToggleGuessPool,
+    CycleGame,
+    OnKeyDown(KeyEvent),
+}
+
+impl Component for MultiApp {
+    type Message = Msg;
+    type Properties = MultiAppProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let num_boards = ctx.props().num_boards;
+        let mut out = Self {
+            solver: MultiSolver::with_num_boards(num_boards),
+            recommendations: Vec::default(),
+            filled_guess: vec![None; WORD_SIZE],
+            filled_colors: vec![Colorings::new(WORD_SIZE); num_boards],
+            keydown_listener: KeyListener::create(ctx.link().callback(Msg::OnKeyDown))
+                .expect("should be able to attach key listener"),
+        };
+        out.update_recommendations();
+        out
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        log::debug!("multi app msg {:?}", &msg);
+        use Msg::*;
+        match msg {
+            PickRecommendation(recommendation) => {
+                for (src, target) in recommendation.chars().zip(self.filled_guess.iter_mut()) {
+                    *target = Some(src);
+                }
+                true
+            }
+            UpdateColoring(board, idx) => {
+                if self.solver.can_guess() {
+                    let src = &mut self.filled_colors[board][idx];
+                    *src = next_coloring(*src);
+                    true
+                } else {
+                    false
+                }
+            }
+            MakeGuess => self.make_guess(),
+            UndoGuess => self.undo_guess(),
+            ClearGuess => {
+                if self.has_any_guess_state() {
+                    self.clear_guess();
+                } else {
+                    self.solver.reset();
+                    self.update_recommendations();
+                }
+                true
+            }
+            ToggleGuessPool => {
+                self.solver.set_guess_pool(match self.solver.guess_pool() {
+                    GuessPool::RemainingPossibilities => GuessPool::AllowedWords,
+                    GuessPool::AllowedWords => GuessPool::RemainingPossibilities,
+                });
+                self.update_recommendations();
+                true
+            }
+            CycleGame => {
+                ctx.props().on_cycle_game.emit(());
+                false
+            }
+            OnKeyDown(mut event) => self.handle_keydown(&mut event),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        // a different number of boards is a completely different game (the callback can change
+        // whenever App re-renders, which shouldn't reset anything)
+        let num_boards = ctx.props().num_boards;
+        if num_boards == self.solver.num_boards() {
+            return false;
+        }
+
+        self.solver = MultiSolver::with_num_boards(num_boards);
+        self.filled_colors = vec![Colorings::new(WORD_SIZE); num_boards];
+        self.clear_guess();
+        self.update_recommendations();
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <>
+                {self.show_game(ctx)}
+                {self.show_recommendation_html(ctx)}
+            </>
+        }
+    }
+}
+
+impl MultiApp {
+    fn update_recommendations(&mut self) {
+        self.recommendations.clear();
+        self.recommendations
+            .extend(self.solver.top_k_guesses::<{ N_RECOMMENDATIONS }>());
+    }
+
+    fn game_name(&self) -> &'static str {
+        MULTI_BOARD_GAMES
+            .iter()
+            .find(|(num_boards, _)| *num_boards == self.solver.num_boards())
+            .map(|(_, name)| *name)
+            .unwrap_or("Multi-board Wordle")
+    }
+
+    fn show_game(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="game-ctr multi">
+                <h1 class="title">
+                    <img alt="[W]" src="static/favicon.png" class="logo" />
+                    {"Joey's Wordle Solver"}
+                </h1>
+                <p class="tagline">
+                    {format!(
+                        "Solving {} ({} boards at once). Type your guess once, then enter the \
+                        colors on each board.",
+                        self.game_name(),
+                        self.solver.num_boards(),
+                    )}
+                </p>
+                <div class="game">
+                    {self.show_guess_row(ctx)}
+                </div>
+                <div class="boards">
+                    {
+                        (0..self.solver.num_boards())
+                            .map(|board| self.show_board(ctx, board))
+                            .collect::<Html>()
+                    }
+                </div>
+            </div>
+        }
+    }
+
+    fn show_guess_row(&self, ctx: &Context<Self>) -> Html {
+        let can_play = self.solver.can_guess();
+        html! {
+            <div class={classes!("game-row", "shared", if can_play { "active" } else { "inactive" })}>
+                {
+                    self.filled_guess.iter().map(|chr| html! {
+                        <div class={classes!(
+                            "game-cell",
+                            "empty",
+                            chr.map(|_| "filled").unwrap_or("unfilled"))}>
+                            { chr.unwrap_or(' ') }
+                        </div>
+                    }).collect::<Html>()
+                }
+                <div class="buttons">
+                    {Self::wordle_button(
+                        ctx,
+                        "reset-button",
+                        "❌",
+                        self.has_any_guess_state() || self.solver.num_guesses() > 0,
+                        Msg::ClearGuess)}
+                    if can_play {
+                        {Self::wordle_button(
+                            ctx,
+                            "confirm-button",
+                            "✔️",
+                            self.guess_str().is_some() && self.guess_problem().is_none(),
+                            Msg::MakeGuess)}
+                    }
+                    {Self::wordle_button(
+                        ctx,
+                        "undo-button",
+                        "↩️",
+                        self.solver.num_guesses() > 0,
+                        Msg::UndoGuess)}
+                </div>
+                if let Some(problem) = self.guess_problem().filter(|_| can_play) {
+                    <div class="problem">{problem}</div>
+                }
+            </div>
+        }
+    }
+
+    fn show_board(&self, ctx: &Context<Self>, board: usize) -> Html {
+        let solver = &self.solver.boards()[board];
+        let show_active = self.solver.can_guess() && !solver.is_solved();
+        html! {
+            <div class={classes!("game", "board", solver.is_solved().then_some("solved"))}>
+                {
+                    solver.iter_guesses().map(|guess| html! {
+                        <div class="game-row filled inactive">
+                            {
+                                guess.word.letters().iter().enumerate().map(|(idx, chr)| html! {
+                                    <div class={classes!(
+                                        "game-cell",
+                                        "filled",
+                                        coloring_class(guess.coloring[idx]))}>
+                                        {*chr}
+                                    </div>
+                                }).collect::<Html>()
+                            }
+                        </div>
+                    }).collect::<Html>()
+                }
+                if show_active {
+                    <div class="game-row active">
+                        {
+                            self.filled_guess.iter().enumerate().map(|(idx, chr)| html! {
+                                <div
+                                    class={classes!(
+                                        "game-cell",
+                                        coloring_class(self.filled_colors[board][idx]))}
+                                    onclick={ctx
+                                        .link()
+                                        .callback(move |_| Msg::UpdateColoring(board, idx))}>
+                                    { chr.unwrap_or(' ') }
+                                </div>
+                            }).collect::<Html>()
+                        }
+                    </div>
+                }
+                <div class="board-status">{self.board_status(board)}</div>
+            </div>
+        }
+    }
+
+    fn board_status(&self, board: usize) -> String {
+        let solver = &self.solver.boards()[board];
+        if solver.is_solved() {
+            format!("solved in {}", solver.num_guesses())
+        } else {
+            format!("{} possible words", solver.num_remaining_possibilities())
+        }
+    }
+
+    fn show_recommendation_html(&self, ctx: &Context<Self>) -> Html {
+        let allow_probes = self.solver.guess_pool() == GuessPool::AllowedWords;
+        html! {
+            <div class="suggestions">
+                <div class="title">{"Suggestions"}</div>
+                <div class="settings">
+                    <div
+                        class="click-text toggle"
+                        onclick={ ctx.link().callback(|_| Msg::ToggleGuessPool) }>
+                        { if allow_probes { "☑ " } else { "☐ " } }
+                        {"suggest words that can't be the answer"}
+                    </div>
+                    <div
+                        class="click-text toggle"
+                        onclick={ ctx.link().callback(|_| Msg::CycleGame) }>
+                        { format!("↻ playing {}", self.game_name()) }
+                    </div>
+                    <div class="legend">{"★ = could be the answer on some board"}</div>
+                </div>
+                <div class="list">
+                    if self.solver.can_guess() {
+                        {
+                            self.recommendations
+                                .iter()
+                                .enumerate()
+                                .map(|(idx, item)| Self::show_recommendation_item(ctx, idx, item))
+                                .collect::<Html>()
+                        }
+                    } else {
+                        <div class="empty-msg">
+                            {"This game is complete, press the X button to reset"}
+                        </div>
+                    }
+                </div>
+            </div>
+        }
+    }
+
+    fn show_recommendation_item(
+        ctx: &Context<Self>,
+        idx: usize,
+        item: &ScoredCandidate<'static>,
+    ) -> Html {
+        let word = item.word;
+        html! {
+            <div
+                class={classes!(
+                    "item",
+                    if item.is_possible_answer { "possible" } else { "probe" }
+                )}
+                onclick={ ctx
+                    .link()
+                    .callback(move |_| Msg::PickRecommendation(word.to_string()))}>
+                <div class="ordinal">{format!("#{:02}", idx + 1)}</div>
+                <div class="word">{word}</div>
+                <div class="possible-marker">
+                    { if item.is_possible_answer { "★" } else { "" } }
+                </div>
+                <div class="details">
+                    <span class="score">{format!("{:.2}", item.score.abs)}</span>
+                    <span class="expected-info" title="expected info, summed over the boards">
+                        {format!("{:.2}", item.score.expected_info)}
+                    </span>
+                </div>
+            </div>
+        }
+    }
+
+    fn wordle_button(
+        ctx: &Context<Self>,
+        c: &'static str,
+        emoji: &'static str,
+        enabled: bool,
+        msg: Msg,
+    ) -> Html {
+        html! {
+            <div
+                class={classes!("button", c, if enabled { "enabled" } else { "disabled" })}
+                onclick={ctx.link().callback(move|_| msg.clone())}>
+                {emoji}
+            </div>
+        }
+    }
+
+    fn guess_str(&self) -> Option<String> {
+        self.filled_guess.iter().copied().collect()
+    }
+
+    /// Explains why the (completely filled) guess cannot be submitted, if it can't be
+    fn guess_problem(&self) -> Option<String> {
+        let guess = self.guess_str()?;
+        if !self.solver.boards()[0].is_guess_permitted(&guess) {
+            return Some("not in word list".to_string());
+        }
+
+        self.solver
+            .unsolved_boards()
+            .find(|board| !self.filled_colors[*board].is_consistent_with_guess(&guess))
+            .map(|board| format!("wordle can't show the colors on board {}", board + 1))
+    }
+
+    fn has_any_guess_state(&self) -> bool {
+        self.filled_guess.iter().any(Option::is_some)
+            || self
+                .filled_colors
+                .iter()
+                .any(|colors| colors.as_slice().iter().any(|c| c != &Coloring::Excluded))
+    }
+
+    fn clear_guess(&mut self) {
+        self.filled_guess = vec![None; WORD_SIZE];
+        for colors in &mut self.filled_colors {
+            *colors = Colorings::new(WORD_SIZE);
+        }
+    }
+
+    fn make_guess(&mut self) -> bool {
+        let guess = match self.guess_str() {
+            Some(guess) if self.solver.can_guess() && self.guess_problem().is_none() => guess,
+            _ => return false,
+        };
+
+        if let Err(err) = self.solver.make_guess(&guess, &self.filled_colors) {
+            log::warn!("weird error when guessing {:?} {:?}", guess, err);
+            return false;
+        }
+
+        self.clear_guess();
+        self.update_recommendations();
+        true
+    }
+
+    /// Removes the last guess & puts it back into the guess row (with each board's colors)
+    fn undo_guess(&mut self) -> bool {
+        let undone = self.solver.undo_last_guess();
+        let word = match undone.iter().flatten().next() {
+            Some(guess) => guess.word,
+            None => return false,
+        };
+
+        self.clear_guess();
+        for (idx, chr) in word.letters().iter().enumerate() {
+            self.filled_guess[idx] = Some(*chr);
+        }
+        for (colors, guess) in self.filled_colors.iter_mut().zip(undone) {
+            if let Some(guess) = guess {
+                *colors = guess.coloring;
+            }
+        }
+
+        self.update_recommendations();
+        true
+    }
+
+    fn handle_keydown(&mut self, event: &mut KeyEvent) -> bool {
+        if event.is_control_key() || !self.solver.can_guess() {
+            return false;
+        }
+
+        let handled = match event.code() {
+            "Backspace" => match self.filled_guess.iter().rposition(Option::is_some) {
+                Some(idx) => {
+                    self.filled_guess[idx] = None;
+                    true
+                }
+                None => false,
+            },
+            "Enter" => self.make_guess(),
+            // all letter keys are of the form "KeyA" or "KeyB" etc
+            code if code.starts_with("Key") && code.len() == 4 => {
+                let letter = code.as_bytes()[3] as char;
+                match self.filled_guess.iter().position(Option::is_none) {
+                    Some(idx) if letter.is_ascii_uppercase() => {
+                        self.filled_guess[idx] = Some(letter.to_ascii_lowercase());
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+
+        if handled {
+            event.prevent_default();
+        }
+
+        handled
+    }
+}
+
+/// The css class which colors a tile
+fn coloring_class(coloring: Coloring) -> &'static str {
+    match coloring {
+        Coloring::Excluded => "c-excluded",
+        Coloring::Misplaced => "c-misplaced",
+        Coloring::Correct => "c-correct",
+    }
+}
@@ -0,0 +1,422 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{color::*, prelude::*, scoring::*};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+///
+/// How many of the best candidates (by the size of the bucket the host would keep) the Solver
+/// ranks using an AdversarySearch, when playing against an adversarial host
+///
+pub const ADVERSARY_SEARCH_CANDIDATES: usize = 8;
+
+///
+/// How many guesses the AdversarySearch tries at each step after the first (see AdversarySearch)
+///
+pub const DEFAULT_ADVERSARY_SEARCH_WIDTH: usize = 3;
+
+///
+/// Describes how the host of the game picks the answer.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum HostModel {
+    /// The answer is picked before the game starts (like Wordle), so every possible answer is
+    /// weighted by how likely it is to be picked
+    #[default]
+    Fair,
+    /// The host avoids committing to an answer for as long as it can, by showing the coloring which
+    /// keeps the most possible answers alive after each guess (like Absurdle)
+    Adversarial,
+}
+
+///
+/// Picks the coloring that an adversarial host shows for a guess, given how many of the possible
+/// answers produce each coloring (indexed by code, like ColoringBuckets::sizes).
+///
+/// The host keeps the largest bucket. When buckets are tied, it keeps the one which reveals the
+/// least: the fewest Correct letters, then the fewest Misplaced letters, then the lowest code.
+///
+pub fn adversary_coloring(word_size: usize, sizes: &[u32]) -> Colorings {
+    let count =
+        |coloring: &Colorings, kind| coloring.as_slice().iter().filter(|c| **c == kind).count();
+    sizes
+        .iter()
+        .enumerate()
+        .filter(|(_, size)| **size > 0)
+        .map(|(code, size)| {
+            let coloring = Colorings::from_code(code as ColoringCode, word_size)
+                .expect("every bucket is indexed by a valid code");
+            (*size, coloring)
+        })
+        .max_by_key(|(size, coloring)| {
+            (
+                *size,
+                Reverse(count(coloring, Coloring::Correct)),
+                Reverse(count(coloring, Coloring::Misplaced)),
+                Reverse(coloring.to_code()),
+            )
+        })
+        .map(|(_, coloring)| coloring)
+        .expect("at least one possible answer must remain")
+}
+
+///
+/// Plays the host of Absurdle: there is no answer, instead every guess is colored by
+/// adversary_coloring, and only the answers which agree with that coloring remain. The game is won
+/// when a single answer remains and it is guessed.
+///
+#[derive(Clone, Debug)]
+pub struct AbsurdleReferee<'a> {
+    remaining: Vec<&'a str>,
+}
+
+impl<'a> AbsurdleReferee<'a> {
+    pub fn new(answers: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            remaining: answers.into_iter().collect(),
+        }
+    }
+
+    ///
+    /// Colors the guess, and eliminates every answer which would have been colored differently
+    ///
+    pub fn respond(&mut self, guess: &str) -> Colorings {
+        let (coloring, kept) = keep_bucket(guess, &self.remaining);
+        self.remaining = kept;
+        coloring
+    }
+
+    ///
+    /// The answers which agree with every response so far
+    ///
+    pub fn remaining(&self) -> &[&'a str] {
+        &self.remaining
+    }
+}
+
+///
+/// Finds how many turns it takes to force a win against an adversarial host.
+///
+/// The host is deterministic (see adversary_coloring), so there is no probability involved: each
+/// guess leads to exactly one set of remaining answers, and the number of turns is:
+///
+///   T(S, g) = 1 if the host colors g all green, otherwise 1 + min over guesses g' of T(S_g, g')
+///
+/// Where S_g is the bucket which the host keeps. Searching every guess at every step is far too
+/// expensive, so after the first guess only the possible answers are considered (every one of
+/// them eliminates at least itself, so the search always ends), and only the `width` which leave
+/// the host with the smallest bucket are searched. This makes the output an upper bound: a win is
+/// always possible in that many turns, but it may be possible in fewer.
+///
+/// The same sets of answers are reached in many different ways, so T(S) is memoized on the set.
+/// The guesses at each step are also pruned against the best number of turns found so far, since
+/// a set of more than one answer always takes at least 2 more turns.
+///
+#[derive(Clone, Debug)]
+pub struct AdversarySearch<'a> {
+    /// The possible answers, a set of answers is a sorted list of indexes into this Vec
+    answers: Vec<&'a str>,
+    /// How many guesses are searched at each step
+    width: usize,
+    /// Memoized output of solve(set, limit), see Turns
+    memo: HashMap<Vec<u32>, Turns>,
+}
+
+///
+/// The output of a search which was limited to some number of turns. A search can stop early once
+/// it knows that the set needs at least `limit` turns, so the exact number isn't always known.
+///
+#[derive(Copy, Clone, Debug)]
+enum Turns {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl<'a> AdversarySearch<'a> {
+    pub fn new(answers: impl IntoIterator<Item = &'a str>, width: usize) -> Self {
+        assert!(
+            width > 0,
+            "at least one guess must be searched at each step"
+        );
+        Self {
+            answers: answers.into_iter().collect(),
+            width,
+            memo: HashMap::new(),
+        }
+    }
+
+    ///
+    /// The number of turns (including this one) it takes to force a win if we make this guess now
+    ///
+    pub fn turns_to_win(&mut self, guess: &str) -> usize {
+        if self.answers.is_empty() {
+            return 0;
+        }
+
+        let all: Vec<u32> = (0..self.answers.len() as u32).collect();
+        let (coloring, kept) = self.keep_bucket(guess, &all);
+        if coloring.is_correct() {
+            1
+        } else {
+            1 + self.solve(kept, usize::MAX)
+        }
+    }
+
+    ///
+    /// Computes min T(S, g) for the answers S, where g is one of the answers. If that is limit or
+    /// more, the search may stop early and only return some lower bound which is at least limit.
+    ///
+    fn solve(&mut self, set: Vec<u32>, limit: usize) -> usize {
+        // with more than one answer left, the host never has to color a guess all green (the other
+        // answers make a bucket which is at least as large), so only a single answer is a win
+        if set.len() == 1 {
+            return 1;
+        }
+
+        match self.memo.get(&set) {
+            Some(Turns::Exactly(turns)) => return *turns,
+            Some(Turns::AtLeast(turns)) if *turns >= limit => return *turns,
+            _ => {}
+        }
+
+        // the sort is stable, so guesses which keep the same number of answers stay in rank order
+        let mut kept: Vec<Vec<u32>> = set
+            .iter()
+            .map(|guess| self.keep_bucket(self.answers[*guess as usize], &set).1)
+            .collect();
+        kept.sort_by_key(|kept| kept.len());
+
+        let mut best = limit;
+        for kept in kept.into_iter().take(self.width) {
+            // a single answer takes 1 more turn, and any other set takes at least 2
+            let fewest_turns = if kept.len() == 1 { 2 } else { 3 };
+            if fewest_turns >= best {
+                continue;
+            }
+
+            best = best.min(1 + self.solve(kept, best - 1));
+        }
+
+        let turns = if best < limit {
+            Turns::Exactly(best)
+        } else {
+            Turns::AtLeast(limit)
+        };
+        self.memo.insert(set, turns);
+        best
+    }
+
+    ///
+    /// Colors the guess like an adversarial host would, returning that coloring and the answers in
+    /// the set which produce it
+    ///
+    fn keep_bucket(&self, guess: &str, set: &[u32]) -> (Colorings, Vec<u32>) {
+        let codes: Vec<ColoringCode> = set
+            .iter()
+            .map(|answer| {
+                Colorings::with_guess_answer(guess, self.answers[*answer as usize]).to_code()
+            })
+            .collect();
+        let mut sizes = vec![0; Colorings::num_states(num_letters(guess))];
+        for code in &codes {
+            sizes[*code as usize] += 1;
+        }
+
+        let coloring = adversary_coloring(num_letters(guess), &sizes);
+        let kept_code = coloring.to_code();
+        let kept = set
+            .iter()
+            .zip(codes)
+            .filter(|(_, code)| *code == kept_code)
+            .map(|(answer, _)| *answer)
+            .collect();
+
+        (coloring, kept)
+    }
+}
+
+///
+/// Colors the guess like an adversarial host would, returning that coloring and the answers which
+/// produce it
+///
+fn keep_bucket<'w>(guess: &str, answers: &[&'w str]) -> (Colorings, Vec<&'w str>) {
+    let buckets = ColoringBuckets::new(guess, answers.iter().map(|answer| (*answer, 0.0)));
+    let coloring = adversary_coloring(num_letters(guess), &buckets.sizes);
+    let kept = answers
+        .iter()
+        .copied()
+        .filter(|answer| Colorings::with_guess_answer(guess, answer) == coloring)
+        .collect();
+
+    (coloring, kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordle::{Coloring::*, GameConfig, GuessPool, HostModel, Solver};
+    use std::time::{Duration, Instant};
+
+    /// Answers which only differ in their first letter, which lets the host hold out for a long
+    /// time, plus chomp & bloke, which each rule out several of them at once
+    fn host_words() -> Vec<String> {
+        [
+            "latch", "batch", "catch", "hatch", "match", "patch", "watch", "chomp", "bloke",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_referee_keeps_largest_bucket() {
+        let words = ["batch", "catch", "hatch", "match", "patch", "watch"];
+        let mut referee = AbsurdleReferee::new(words);
+
+        // chomp splits the words into {batch, hatch, watch}, {catch}, {match}, {patch}
+        assert_eq!(
+            referee.respond("chomp"),
+            [Misplaced, Misplaced, Excluded, Excluded, Excluded].into()
+        );
+        assert_eq!(referee.remaining(), &["batch", "hatch", "watch"]);
+
+        // catch & match are tied, so the host keeps the one with fewer green letters
+        let mut referee = AbsurdleReferee::new(["catch", "match"]);
+        assert_eq!(
+            referee.respond("chomp"),
+            [Misplaced, Misplaced, Excluded, Misplaced, Excluded].into()
+        );
+        assert_eq!(referee.remaining(), &["match"]);
+        assert!(referee.respond("match").is_correct());
+    }
+
+    #[test]
+    fn test_adversary_search_turns_to_win() {
+        let words = ["batch", "catch", "hatch", "match", "patch", "watch"];
+        let mut search = AdversarySearch::new(words, DEFAULT_ADVERSARY_SEARCH_WIDTH);
+
+        // every guess from the set leaves the other 5 words, which can only be guessed one by one
+        assert_eq!(search.turns_to_win("batch"), 6);
+        // chomp leaves {batch, hatch, watch}, which takes another 3 turns
+        assert_eq!(search.turns_to_win("chomp"), 4);
+    }
+
+    #[test]
+    fn test_adversary_search_matches_exhaustive_search() {
+        // T(S) without the memo or the pruning
+        fn exhaustive(answers: &[&str], width: usize) -> usize {
+            if answers.len() == 1 {
+                return 1;
+            }
+
+            let mut kept: Vec<Vec<&str>> = answers
+                .iter()
+                .map(|guess| keep_bucket(guess, answers).1)
+                .collect();
+            kept.sort_by_key(|kept| kept.len());
+            1 + kept
+                .iter()
+                .take(width)
+                .map(|kept| exhaustive(kept, width))
+                .min()
+                .unwrap()
+        }
+
+        let words = [
+            "bills", "batch", "catch", "fills", "hatch", "hills", "match", "mills", "patch",
+            "pills", "watch", "wills",
+        ];
+        for width in [1, DEFAULT_ADVERSARY_SEARCH_WIDTH, words.len()] {
+            let mut search = AdversarySearch::new(words, width);
+            for guess in ["bills", "chomp", "spill", "tacit"] {
+                let (coloring, kept) = keep_bucket(guess, &words);
+                let expected = if coloring.is_correct() {
+                    1
+                } else {
+                    1 + exhaustive(&kept, width)
+                };
+                assert_eq!(
+                    search.turns_to_win(guess),
+                    expected,
+                    "guess={}, width={}",
+                    guess,
+                    width
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_adversary_search_is_fast_with_many_answers() {
+        let words = [
+            "bills", "cills", "dills", "fills", "gills", "hills", "jills", "kills", "lills",
+            "mills", "nills", "pills", "rills", "sills", "tills", "vills", "wills", "yills",
+            "zills",
+        ];
+        let start_at = Instant::now();
+        let mut search = AdversarySearch::new(words, DEFAULT_ADVERSARY_SEARCH_WIDTH);
+
+        // every guess from the set only eliminates itself, so they are guessed one by one
+        assert_eq!(search.turns_to_win("bills"), words.len());
+        // without the memo, the search would branch 3 ways for each of the 18 remaining turns
+        assert!(
+            start_at.elapsed() < Duration::from_secs(1),
+            "took {:?}",
+            start_at.elapsed()
+        );
+    }
+
+    #[test]
+    fn test_adversarial_host_against_referee() {
+        let words = host_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_host_model(HostModel::Adversarial);
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        let mut referee = AbsurdleReferee::new(words.iter().map(|w| w.as_str()));
+
+        // chomp leaves the host with the fewest words: {latch, batch, hatch, watch}. The search only
+        // guesses possible answers after the first guess, so it goes through those one by one
+        let first = solver.top_k_guesses::<9>().next().unwrap();
+        assert_eq!(first.word, "chomp");
+        assert_eq!(first.score.expected_guesses, Some(5.0));
+
+        while !solver.is_solved() {
+            let guess = solver.top_k_guesses::<1>().next().unwrap().word;
+            let predicted = solver.predict_adversary_coloring(guess);
+            let coloring = referee.respond(guess);
+            assert_eq!(predicted, Some(coloring), "{}", guess);
+
+            solver.make_guess(guess, coloring).unwrap();
+            let remaining: Vec<_> = solver
+                .remaining_possibilities()
+                .iter()
+                .map(|id| solver.word_bank().word(id))
+                .collect();
+            assert_eq!(remaining, referee.remaining());
+        }
+        // but the second turn can use bloke to split up latch & batch, which saves a turn
+        assert_eq!(solver.num_guesses(), 4);
+    }
+}
@@ -0,0 +1,291 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::prelude::*;
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+///
+/// The letters which words in a game are made of. Every letter has an index (its position in the
+/// alphabet), which is used to count letters & to store words compactly (see CompressedWord).
+///
+/// The original wordle uses the 26 letters a-z, but other languages add their own letters (like ñ
+/// in Spanish) and some games aren't about words at all (Nerdle guesses are equations, built from
+/// digits & operators).
+///
+/// The letters are &'static so that an Alphabet (and a GameConfig) can be copied around freely.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    name: &'static str,
+    letters: &'static [char],
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AlphabetErr {
+    #[error("an alphabet needs at least one letter")]
+    Empty,
+    #[error("an alphabet can have at most {} letters, got {0}", MAX_ALPHABET_SIZE)]
+    TooManyLetters(usize),
+    #[error("the letter '{0}' is in the alphabet more than once")]
+    DuplicateLetter(char),
+    #[error("'{0}' can't be a letter (letters must be lowercase & visible)")]
+    InvalidLetter(char),
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+impl Alphabet {
+    pub const ENGLISH: Alphabet = Alphabet {
+        name: "english",
+        letters: &[
+            'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q',
+            'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+        ],
+    };
+
+    /// Accented vowels are written without their accents, but ñ is a letter of its own
+    pub const SPANISH: Alphabet = Alphabet {
+        name: "spanish",
+        letters: &[
+            'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'ñ', 'o', 'p',
+            'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+        ],
+    };
+
+    pub const GERMAN: Alphabet = Alphabet {
+        name: "german",
+        letters: &[
+            'a', 'ä', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'ö',
+            'p', 'q', 'r', 's', 'ß', 't', 'u', 'ü', 'v', 'w', 'x', 'y', 'z',
+        ],
+    };
+
+    /// Also used for Finnish
+    pub const SWEDISH: Alphabet = Alphabet {
+        name: "swedish",
+        letters: &[
+            'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q',
+            'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'å', 'ä', 'ö',
+        ],
+    };
+
+    /// The accented vowels are separate letters in Icelandic
+    pub const ICELANDIC: Alphabet = Alphabet {
+        name: "icelandic",
+        letters: &[
+            'a', 'á', 'b', 'd', 'ð', 'e', 'é', 'f', 'g', 'h', 'i', 'í', 'j', 'k', 'l', 'm', 'n',
+            'o', 'ó', 'p', 'r', 's', 't', 'u', 'ú', 'v', 'x', 'y', 'ý', 'þ', 'æ', 'ö',
+        ],
+    };
+
+    /// The symbols of Nerdle, where every guess is an equation like "12+35=47"
+    pub const NERDLE: Alphabet = Alphabet {
+        name: "nerdle",
+        letters: &[
+            '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '+', '-', '*', '/', '=',
+        ],
+    };
+
+    /// Every alphabet which is built in, see named
+    pub const BUILT_IN: [Alphabet; 6] = [
+        Self::ENGLISH,
+        Self::SPANISH,
+        Self::GERMAN,
+        Self::SWEDISH,
+        Self::ICELANDIC,
+        Self::NERDLE,
+    ];
+
+    ///
+    /// Creates a custom alphabet, checking that each letter is unique, lowercase and visible (see
+    /// is_normal_wordle_char). The order of the letters decides their index.
+    ///
+    pub fn new(name: &'static str, letters: &'static [char]) -> Result<Self, AlphabetErr> {
+        if letters.is_empty() {
+            return Err(AlphabetErr::Empty);
+        }
+
+        if letters.len() > MAX_ALPHABET_SIZE {
+            return Err(AlphabetErr::TooManyLetters(letters.len()));
+        }
+
+        for (idx, letter) in letters.iter().enumerate() {
+            if !is_normal_wordle_char(*letter) {
+                return Err(AlphabetErr::InvalidLetter(*letter));
+            }
+
+            if letters[..idx].contains(letter) {
+                return Err(AlphabetErr::DuplicateLetter(*letter));
+            }
+        }
+
+        Ok(Self { name, letters })
+    }
+
+    ///
+    /// Finds the built-in alphabet with the name (like "spanish")
+    ///
+    pub fn named(name: &str) -> Option<Self> {
+        Self::BUILT_IN
+            .into_iter()
+            .find(|alphabet| alphabet.name == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The number of letters in the alphabet
+    pub fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+    }
+
+    /// Every letter, in the order of their index
+    pub fn letters(&self) -> &'static [char] {
+        self.letters
+    }
+
+    /// The letter with the given index
+    pub fn letter(&self, idx: usize) -> char {
+        self.letters[idx]
+    }
+
+    /// The index of the letter within the alphabet, or None if it isn't one of the letters
+    pub fn index_of(&self, letter: char) -> Option<usize> {
+        self.letters.iter().position(|l| *l == letter)
+    }
+
+    pub fn contains(&self, letter: char) -> bool {
+        self.index_of(letter).is_some()
+    }
+
+    ///
+    /// Whether or not the string is a wordle_str which only uses letters from this alphabet. Use
+    /// GameConfig::is_word to also check the length against a particular game.
+    ///
+    pub fn is_word(&self, word: &str) -> bool {
+        is_wordle_str(word) && word.chars().all(|c| self.contains(c))
+    }
+
+    ///
+    /// Returns the number of times each letter of the alphabet occurs in the word, indexed by the
+    /// index of the letter (see index_of). Characters which aren't in the alphabet are not counted.
+    ///
+    pub fn count_letters(&self, word: &str) -> [usize; MAX_ALPHABET_SIZE] {
+        let mut out = [0; MAX_ALPHABET_SIZE];
+        for idx in word.chars().filter_map(|c| self.index_of(c)) {
+            out[idx] += 1;
+        }
+
+        out
+    }
+
+    ///
+    /// Like normalize_wordle_word, but accented letters which aren't in this alphabet are replaced
+    /// by the letter without the accent (if that one is in the alphabet). For example "canción" is
+    /// "cancion" in Spanish, but "niño" keeps its ñ.
+    ///
+    /// You should always verify that the output of this function passes is_word.
+    ///
+    pub fn normalize(&self, word: &str) -> String {
+        normalize_wordle_word(word)
+            .chars()
+            .map(|c| match strip_accent(c) {
+                Some(plain) if !self.contains(c) && self.contains(plain) => plain,
+                _ => c,
+            })
+            .collect()
+    }
+}
+
+impl Display for Alphabet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// The letter without its accent, for the (lowercase) accented letters of the latin alphabet
+fn strip_accent(letter: char) -> Option<char> {
+    Some(match letter {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'ç' => 'c',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_follows_alphabet() {
+        assert_eq!(Alphabet::SPANISH.normalize(" Canción "), "cancion");
+        assert_eq!(Alphabet::SPANISH.normalize("NIÑOS"), "niños");
+        assert_eq!(Alphabet::ENGLISH.normalize("niños"), "ninos");
+        assert_eq!(Alphabet::ICELANDIC.normalize("Hlýða"), "hlýða");
+
+        assert!(Alphabet::SPANISH.is_word("niños"));
+        assert!(!Alphabet::ENGLISH.is_word("niños"));
+        assert!(Alphabet::NERDLE.is_word("12+35=47"));
+        assert!(!Alphabet::NERDLE.is_word("12+35=4a"));
+    }
+
+    #[test]
+    fn test_count_letters() {
+        let counts = Alphabet::SWEDISH.count_letters("sågär");
+        assert_eq!(counts[Alphabet::SWEDISH.index_of('å').unwrap()], 1);
+        assert_eq!(counts[Alphabet::SWEDISH.index_of('ä').unwrap()], 1);
+        assert_eq!(counts.iter().sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        assert!(Alphabet::new("abc", &['a', 'b', 'c']).is_ok());
+        assert_eq!(Alphabet::new("none", &[]), Err(AlphabetErr::Empty));
+        assert_eq!(
+            Alphabet::new("dupes", &['a', 'b', 'a']),
+            Err(AlphabetErr::DuplicateLetter('a'))
+        );
+        assert_eq!(
+            Alphabet::new("upper", &['a', 'B']),
+            Err(AlphabetErr::InvalidLetter('B'))
+        );
+        assert_eq!(Alphabet::named("nerdle"), Some(Alphabet::NERDLE));
+    }
+}
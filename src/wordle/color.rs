@@ -31,15 +31,15 @@ use std::ops::{Index, IndexMut};
 
 ///
 /// Any set of colorings can be converted to a "code" which uniquely identifies that specific
-/// coloring. This type is the number we use to store that code (and we pick u8 because the range is
-/// 0 -> 3^5=243 for 3 colorings in a 5 letter puzzle).
+/// coloring. This type is the number we use to store that code (and we pick u16 because the range is
+/// 0 -> 3^8=6561 for 3 colorings in the longest (8 letter) puzzle).
 ///
-pub type ColoringCode = u8;
+pub type ColoringCode = u16;
 
 ///
 /// The three different colors that a wordle square can be...
 ///   * Excluded = the letter is not in the answer (also indicates no further instances of a letter
-///                when another square with the same letter is colored misplaced/correct)
+///     when another square with the same letter is colored misplaced/correct)
 ///   * Misplaced = the letter is in the answer, but not in this position
 ///   * Correct = the letter is in the answer at this position
 ///
@@ -86,81 +86,143 @@ impl Coloring {
             Correct => "🟩",
         }
     }
-}
 
-/// An array of Colorings, one for each square in the puzzle.
-pub type ColoringsArray = [Coloring; WORD_SIZE];
+    /// The name of the color that Wordle uses for the coloring (like "yellow" for Misplaced)
+    pub fn color_name(&self) -> &'static str {
+        use Coloring::*;
+        match self {
+            Excluded => "gray",
+            Misplaced => "yellow",
+            Correct => "green",
+        }
+    }
+}
 
-/// The array of Colorings, but in a struct, so that we can attach some useful functions to a
-/// complete set of Colorings.
+///
+/// The Colorings of every square in a guess, in a struct, so that we can attach some useful
+/// functions to a complete set of Colorings.
+///
+/// The squares are stored inline (with room for the longest word size) so that Colorings stays
+/// Copy. Squares past the end of the word are always Excluded, so they never affect Eq or Hash.
+///
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct Colorings(pub ColoringsArray);
+pub struct Colorings {
+    colors: [Coloring; MAX_WORD_SIZE],
+    len: u8,
+}
 
-/// Automatic conversion of [Coloring; WORD_SIZE] -> Colorings
-impl From<ColoringsArray> for Colorings {
-    fn from(arr: ColoringsArray) -> Self {
-        Self(arr)
+/// Automatic conversion of [Coloring; N] -> Colorings (N must be a supported word size)
+impl<const N: usize> From<[Coloring; N]> for Colorings {
+    fn from(arr: [Coloring; N]) -> Self {
+        let mut out = Self::new(N);
+        out.colors[..N].copy_from_slice(&arr);
+        out
     }
 }
 
-/// Delegate indexing of the struct to it's inner value
+/// Delegate indexing of the struct to the squares of the word
 impl Index<usize> for Colorings {
     type Output = Coloring;
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.0[index]
+        &self.as_slice()[index]
     }
 }
 
-/// Delegate mutable indexing of the struct to it's inner value
+/// Delegate mutable indexing of the struct to the squares of the word
 impl IndexMut<usize> for Colorings {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.0[index]
+        &mut self.colors[..self.len as usize][index]
     }
 }
 
 impl Colorings {
-    /// How many different possible colorings are there? In the case of a 5 word puzzle with 3
-    /// colorings it's 3^5=243 possible colorings
-    pub const NUM_STATES: usize = Coloring::NUM.pow(WORD_SIZE as u32);
+    /// Creates Colorings for a word with word_size letters, where every square is Excluded
+    pub fn new(word_size: usize) -> Self {
+        assert!(
+            (MIN_WORD_SIZE..=MAX_WORD_SIZE).contains(&word_size),
+            "unsupported word size {}",
+            word_size
+        );
+
+        Self {
+            colors: [Excluded; MAX_WORD_SIZE],
+            len: word_size as u8,
+        }
+    }
+
+    /// Creates Colorings for a word with word_size letters, where every square is Correct
+    pub fn all_correct(word_size: usize) -> Self {
+        let mut out = Self::new(word_size);
+        out.colors[..word_size].fill(Correct);
+        out
+    }
+
+    /// How many different possible colorings are there for words with word_size letters? In the
+    /// case of a 5 letter puzzle with 3 colorings it's 3^5=243 possible colorings
+    pub fn num_states(word_size: usize) -> usize {
+        Coloring::NUM.pow(word_size as u32)
+    }
+
+    /// The number of squares (the length of the word these Colorings are for)
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Always false, Colorings always have at least MIN_WORD_SIZE squares
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The Coloring of each square, in order
+    pub fn as_slice(&self) -> &[Coloring] {
+        &self.colors[..self.len as usize]
+    }
+
+    /// Whether or not every square is Correct (the guess was the answer)
+    pub fn is_correct(&self) -> bool {
+        self.as_slice().iter().all(|c| *c == Correct)
+    }
 
     ///
     /// Compute what colors would be shown given some guess & answer. For example if the guess was
     /// "tares" and the answer was "scare" we should compute [Excluded, Misplaced, Misplaced, Misplaced, Misplaced]
     ///
     /// This is implemented by:
-    /// * creating an empty [Coloring; WORD_SIZE] where all colors are defaulted to Misplaced
-    /// * computing a "budget" for each letter in the alphabet (based on their frequency in the answer)
-    /// * performing a "GREEN pass" which marks all correctly positioned letters (and updates the budget)
-    /// * performing a "YELLOW pass" to mark all misplaced letters (based on the remaining budget for each letter)
+    /// * creating an empty Colorings where all colors are defaulted to Excluded
+    /// * performing a "GREEN pass" which marks all correctly positioned letters (and uses up those
+    ///   letters of the answer)
+    /// * performing a "YELLOW pass" to mark all misplaced letters (each one uses up the left-most
+    ///   copy of the letter in the answer which hasn't been used yet)
+    ///
+    /// Letters are only compared to each other, so this works the same way for any Alphabet.
     ///
     pub fn with_guess_answer(guess: &str, answer: &str) -> Self {
         assert!(is_wordle_str(answer));
         assert!(is_wordle_str(guess));
 
-        let mut out = Self::default();
-        let mut answer_letter_counts = count_letters(answer);
-        let answer_bytes = answer.as_bytes();
-        let guess_bytes = guess.as_bytes();
+        let (guess_letters, word_size) = letters_of(guess);
+        let (answer_letters, answer_size) = letters_of(answer);
+        assert_eq!(word_size, answer_size);
 
-        // GREEN pass
-        for i in 0..WORD_SIZE {
-            let gc = guess_bytes[i];
-            let ac = answer_bytes[i];
+        let mut out = Self::new(word_size);
+        let mut used = [false; MAX_WORD_SIZE];
 
-            if gc == ac {
-                answer_letter_counts[letter_idx(gc)] -= 1;
+        // GREEN pass
+        for i in 0..word_size {
+            if guess_letters[i] == answer_letters[i] {
+                used[i] = true;
                 out[i] = Correct;
             }
         }
 
         // YELLOW pass
-        for i in 0..WORD_SIZE {
+        for i in 0..word_size {
             if out[i] != Correct {
-                let gc = guess_bytes[i];
-                let counter = &mut answer_letter_counts[letter_idx(gc)];
-                if *counter > 0 {
-                    *counter -= 1;
+                let unused =
+                    (0..word_size).find(|j| !used[*j] && answer_letters[*j] == guess_letters[i]);
+                if let Some(j) = unused {
+                    used[j] = true;
                     out[i] = Misplaced;
                 }
             }
@@ -171,7 +233,7 @@ impl Colorings {
 
     ///
     /// Computes a code that uniquely identifies this particular coloring. These codes are numbers in
-    /// [0, 243) (in the case of a 5 letter puzzle).
+    /// [0, 243) (in the case of a 5 letter puzzle), see num_states.
     ///
     /// We essentially treat the colorings as a 5 digit base-3 number. Each Coloring has an ordinal()
     /// which ranges from [0, 3), and the left-most color is digit 0, next digit 1, etc.
@@ -184,9 +246,9 @@ impl Colorings {
     pub fn to_code(&self) -> ColoringCode {
         let mut out = 0;
         let mut multiplier = 1;
-        for i in 0..WORD_SIZE {
-            out += self[i].ordinal() * multiplier;
-            multiplier *= Coloring::NUM as u8;
+        for color in self.as_slice() {
+            out += color.ordinal() * multiplier;
+            multiplier *= Coloring::NUM as ColoringCode;
         }
         out
     }
@@ -197,33 +259,116 @@ impl Colorings {
     /// This works by treating the code as a base-3 number, and the code is basically identical to
     /// any digit-by-digit processing you've written before.
     ///
-    pub fn from_code(mut code: ColoringCode) -> Option<Self> {
-        let mut out = Self::default();
-        for i in 0..WORD_SIZE {
-            out[i] = Coloring::from_ordinal(code % (Coloring::NUM as u8))?;
-            code /= Coloring::NUM as u8;
+    pub fn from_code(mut code: ColoringCode, word_size: usize) -> Option<Self> {
+        let mut out = Self::new(word_size);
+        for i in 0..word_size {
+            out[i] = Coloring::from_ordinal(code % (Coloring::NUM as ColoringCode))?;
+            code /= Coloring::NUM as ColoringCode;
         }
 
         Some(out)
     }
 
+    ///
+    /// Whether or not Wordle could ever show this coloring for the guess. Some colorings can never
+    /// happen, like marking the second copy of a repeated letter Misplaced while the first copy is
+    /// Excluded (Wordle always colors the left-most copies first).
+    ///
+    /// Correct squares are always possible, so this checks each letter's other copies:
+    /// * the Misplaced copies must all come before the Excluded ones
+    /// * the answer needs a copy of the letter for each Misplaced square, and these can only go in
+    ///   squares which aren't Correct and where the guess has some other letter
+    ///
+    /// A filler letter can go in any square that is left over, so no other checks are needed.
+    ///
+    pub fn is_consistent_with_guess(&self, guess: &str) -> bool {
+        let (letters, word_size) = letters_of(guess);
+        assert_eq!(word_size, self.len());
+
+        let open = self.as_slice().iter().filter(|c| **c != Correct).count();
+        for i in 0..word_size {
+            let letter = letters[i];
+            let is_open_copy = |j: &usize| letters[*j] == letter && self[*j] != Correct;
+            if !is_open_copy(&i) || (0..i).any(|j| is_open_copy(&j)) {
+                // only check each letter once, starting from its first copy which isn't Correct
+                continue;
+            }
+
+            let mut copies = 0;
+            let mut misplaced = 0;
+            let mut seen_excluded = false;
+            for j in (i..word_size).filter(is_open_copy) {
+                copies += 1;
+                match self[j] {
+                    Misplaced if seen_excluded => return false,
+                    Misplaced => misplaced += 1,
+                    _ => seen_excluded = true,
+                }
+            }
+
+            if misplaced > open - copies {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    ///
+    /// Flags the squares which make this coloring impossible for the guess (see
+    /// is_consistent_with_guess). These are the squares which differ from the closest colorings that
+    /// Wordle could show, so recoloring any one of them is a step towards a valid coloring.
+    ///
+    /// Nothing is flagged when the coloring is consistent with the guess.
+    ///
+    pub fn inconsistent_tiles(&self, guess: &str) -> Vec<bool> {
+        let mut out = vec![false; self.len()];
+        let mut closest = usize::MAX;
+        for code in 0..Self::num_states(self.len()) as ColoringCode {
+            let other = Self::from_code(code, self.len()).expect("code should be in range");
+            let distance = (0..self.len()).filter(|i| self[*i] != other[*i]).count();
+            if distance > closest || !other.is_consistent_with_guess(guess) {
+                continue;
+            }
+
+            if distance < closest {
+                closest = distance;
+                out.fill(false);
+            }
+
+            for (i, flag) in out.iter_mut().enumerate() {
+                *flag |= self[i] != other[i];
+            }
+        }
+
+        out
+    }
+
     #[cfg(test)]
-    /// Iterates through all possible [Coloring; 5] configurations
-    fn iter_all_possible() -> IterAllColorings {
-        IterAllColorings::default()
+    /// Iterates through all possible [Coloring; word_size] configurations
+    fn iter_all_possible(word_size: usize) -> IterAllColorings {
+        IterAllColorings {
+            next: Some(Colorings::new(word_size)),
+        }
     }
 }
 
-impl Default for Colorings {
-    fn default() -> Self {
-        Self([Excluded; WORD_SIZE])
+/// The letters of the word (padded to MAX_WORD_SIZE), and how many there are
+fn letters_of(word: &str) -> ([char; MAX_WORD_SIZE], usize) {
+    let mut out = [' '; MAX_WORD_SIZE];
+    let mut len = 0;
+    for (slot, letter) in out.iter_mut().zip(word.chars()) {
+        *slot = letter;
+        len += 1;
     }
+
+    (out, len)
 }
 
 impl Display for Colorings {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for i in 0..WORD_SIZE {
-            f.write_str(self[i].emoji())?;
+        for color in self.as_slice() {
+            f.write_str(color.emoji())?;
         }
 
         Ok(())
@@ -235,15 +380,6 @@ struct IterAllColorings {
     next: Option<Colorings>,
 }
 
-#[cfg(test)]
-impl Default for IterAllColorings {
-    fn default() -> Self {
-        Self {
-            next: Some(Colorings::default()),
-        }
-    }
-}
-
 #[cfg(test)]
 impl Iterator for IterAllColorings {
     type Item = Colorings;
@@ -270,7 +406,7 @@ impl Iterator for IterAllColorings {
             // ...
             //
             // which will eventually exhaust all possible colorings
-            for k in (0..WORD_SIZE).rev() {
+            for k in (0..cur.len()).rev() {
                 match next[k] {
                     Excluded => {
                         next[k] = Misplaced;
@@ -300,7 +436,10 @@ impl Iterator for IterAllColorings {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (Colorings::NUM_STATES, Some(Colorings::NUM_STATES))
+        let num_states = self
+            .next
+            .map_or(0, |next| Colorings::num_states(next.len()));
+        (num_states, Some(num_states))
     }
 }
 
@@ -313,6 +452,7 @@ impl FusedIterator for IterAllColorings {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
     use test_case::test_case;
 
     #[test]
@@ -324,7 +464,7 @@ mod tests {
 
     #[test]
     fn test_colorings_fit_into_code_type() {
-        let num_states = Colorings::NUM_STATES;
+        let num_states = Colorings::num_states(MAX_WORD_SIZE);
         let max_code_rep = ColoringCode::MAX as usize;
         assert!(
             num_states < max_code_rep,
@@ -339,7 +479,7 @@ mod tests {
     #[test]
     fn test_unique_coding_of_colorings() {
         let mut seen = [false; ColoringCode::MAX as usize];
-        for colorings in Colorings::iter_all_possible() {
+        for colorings in Colorings::iter_all_possible(WORD_SIZE) {
             let code = colorings.to_code();
             assert!(
                 !seen[code as usize],
@@ -352,11 +492,11 @@ mod tests {
 
     #[test]
     fn test_reversible_coding_of_colorings() {
-        for colorings in Colorings::iter_all_possible() {
+        for colorings in Colorings::iter_all_possible(WORD_SIZE) {
             let code = colorings.to_code();
             assert_eq!(
                 Some(colorings),
-                Colorings::from_code(code),
+                Colorings::from_code(code, WORD_SIZE),
                 "code {} produced from {:?} should reverse to same colorings",
                 code,
                 colorings,
@@ -380,13 +520,66 @@ mod tests {
     #[test_case("arles", "ledge", [Excluded, Excluded, Misplaced, Misplaced, Excluded])]
     #[test_case("elite", "ledge", [Misplaced, Misplaced, Excluded, Excluded, Correct])]
     #[test_case("ledge", "ledge", [Correct, Correct, Correct, Correct, Correct])]
-    fn test_coloring(guess: &str, answer: &str, expected_coloring: ColoringsArray) {
+    #[test_case("ruse", "sure", [Misplaced, Correct, Misplaced, Correct])]
+    #[test_case("kitten", "mitten", [Excluded, Correct, Correct, Correct, Correct, Correct])]
+    #[test_case("banana", "cabana", [Misplaced, Correct, Excluded, Correct, Correct, Correct])]
+    #[test_case("niños", "señor", [Excluded, Excluded, Correct, Correct, Misplaced])]
+    #[test_case("12+35=47", "12+36=48", [Correct, Correct, Correct, Correct, Excluded, Correct, Correct, Excluded])]
+    fn test_coloring<const N: usize>(guess: &str, answer: &str, expected_coloring: [Coloring; N]) {
+        let expected_coloring = Colorings::from(expected_coloring);
         assert_eq!(
             Colorings::with_guess_answer(guess, answer),
-            Colorings(expected_coloring),
+            expected_coloring,
             "guess={}, answer={}",
             guess,
             answer
         );
+        assert!(expected_coloring.is_consistent_with_guess(guess));
+    }
+
+    #[test_case("speed", [Excluded, Excluded, Excluded, Misplaced, Excluded], [false, false, true, true, false])]
+    #[test_case("aaaab", [Misplaced, Misplaced, Excluded, Excluded, Excluded], [true, true, false, false, false])]
+    #[test_case("speed", [Correct, Correct, Excluded, Misplaced, Correct], [false, false, false, true, false])]
+    #[test_case("parallel", [Excluded, Excluded, Excluded, Excluded, Excluded, Misplaced, Excluded, Excluded], [false, false, false, false, true, true, false, false])]
+    #[test_case("llllllla", [Misplaced, Misplaced, Excluded, Excluded, Excluded, Excluded, Excluded, Excluded], [true, true, false, false, false, false, false, false])]
+    fn test_impossible_coloring<const N: usize>(
+        guess: &str,
+        coloring: [Coloring; N],
+        expected_tiles: [bool; N],
+    ) {
+        let coloring = Colorings::from(coloring);
+        assert!(!coloring.is_consistent_with_guess(guess));
+        assert_eq!(coloring.inconsistent_tiles(guess), expected_tiles);
+    }
+
+    #[test_case("speed")]
+    #[test_case("aaaab")]
+    #[test_case("eerie")]
+    #[test_case("llama")]
+    fn test_consistent_colorings_match_answers(guess: &str) {
+        // every answer which matters can be built from the guess's letters plus one other letter
+        let mut letters: Vec<char> = guess.chars().chain(['z']).collect();
+        letters.sort_unstable();
+        letters.dedup();
+
+        let mut possible = HashSet::new();
+        for mut code in 0..letters.len().pow(WORD_SIZE as u32) {
+            let mut answer = String::new();
+            for _ in 0..WORD_SIZE {
+                answer.push(letters[code % letters.len()]);
+                code /= letters.len();
+            }
+            possible.insert(Colorings::with_guess_answer(guess, &answer));
+        }
+
+        for colorings in Colorings::iter_all_possible(WORD_SIZE) {
+            assert_eq!(
+                possible.contains(&colorings),
+                colorings.is_consistent_with_guess(guess),
+                "guess={}, colorings={:?}",
+                guess,
+                colorings
+            );
+        }
     }
 }
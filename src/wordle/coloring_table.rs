@@ -0,0 +1,273 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{color::*, prelude::*, word_bank::*};
+use std::sync::OnceLock;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    fs,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+///
+/// Where the native tools (gen_all_data, gen_guess_model) keep a ColoringTable between runs. It is
+/// kept out of txt_data/ because it is far too large to embed in the web app.
+///
+#[cfg(not(target_arch = "wasm32"))]
+pub const COLORING_TABLE_CACHE_FILE: &str = "target/coloring_table.bin";
+
+#[cfg(not(target_arch = "wasm32"))]
+const CACHE_FILE_MAGIC: &[u8; 4] = b"WCT1";
+
+///
+/// A ColoringCode which is small enough to fit in a single byte. The table stores these instead of
+/// full ColoringCodes to keep its size down, which limits it to words with at most 5 letters
+/// (3^5=243 colorings).
+///
+pub type CompactColoringCode = u8;
+
+///
+/// Stores the ColoringCode produced by every (guess, answer) pair of words, so that the colorings
+/// only have to be computed once. Words are identified by their WordId, which is their index in the
+/// word list the table was created with (the same as in a WordBank).
+///
+/// Each row (all the colorings for a single guess) is computed lazily, the first time it is needed.
+/// A full table for the default word list is ~220MB, so the table can also be filled completely &
+/// saved to a cache file on native targets.
+///
+#[derive(Debug)]
+pub struct ColoringTable {
+    words: Vec<String>,
+    rows: Vec<OnceLock<Box<[CompactColoringCode]>>>,
+}
+
+impl ColoringTable {
+    ///
+    /// Whether or not every coloring of words with word_size letters fits in a CompactColoringCode
+    ///
+    pub fn supports_word_size(word_size: usize) -> bool {
+        Colorings::num_states(word_size) <= CompactColoringCode::MAX as usize + 1
+    }
+
+    pub fn new(words: &[impl AsRef<str>]) -> Self {
+        assert!(words.len() <= WordBank::MAX_WORDS);
+        assert!(
+            words
+                .iter()
+                .all(|w| Self::supports_word_size(num_letters(w.as_ref()))),
+            "coloring tables only support words with short enough colorings"
+        );
+        Self {
+            words: words.iter().map(|w| w.as_ref().to_string()).collect(),
+            rows: (0..words.len()).map(|_| OnceLock::new()).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    ///
+    /// The colorings for the guess with the given id, indexed by the id of the answer
+    ///
+    pub fn row(&self, guess_id: WordId) -> &[CompactColoringCode] {
+        self.rows[guess_id as usize].get_or_init(|| {
+            let guess = self.words[guess_id as usize].as_str();
+            self.words
+                .iter()
+                .map(|answer| {
+                    Colorings::with_guess_answer(guess, answer).to_code() as CompactColoringCode
+                })
+                .collect()
+        })
+    }
+
+    ///
+    /// The colorings for the guess with the given id, but only if they were already computed
+    ///
+    pub fn computed_row(&self, guess_id: WordId) -> Option<&[CompactColoringCode]> {
+        self.rows[guess_id as usize].get().map(|row| row.as_ref())
+    }
+
+    ///
+    /// The code of the coloring which the guess gets when the answer is... the answer
+    ///
+    pub fn get(&self, guess_id: WordId, answer_id: WordId) -> ColoringCode {
+        self.row(guess_id)[answer_id as usize] as ColoringCode
+    }
+
+    ///
+    /// Computes every row which hasn't been computed yet (on multiple threads, with the "parallel"
+    /// feature enabled)
+    ///
+    pub fn fill(&self) {
+        let ids: Vec<WordId> = self.ids().collect();
+        map_chunks(&ids, 64, |chunk| {
+            for guess_id in chunk {
+                self.row(*guess_id);
+            }
+        });
+    }
+
+    fn ids(&self) -> impl Iterator<Item = WordId> {
+        (0..self.len()).map(|id| id as WordId)
+    }
+
+    ///
+    /// Reads the table from the cache file if it was written for the same words, otherwise builds
+    /// the full table and tries to write it to the cache file for next time.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_or_build(words: &[impl AsRef<str>], path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        if let Ok(Some(table)) = Self::read_cache_file(words, path) {
+            return table;
+        }
+
+        let out = Self::new(words);
+        out.fill();
+        if let Err(err) = out.write_cache_file(path) {
+            log::warn!("unable to write coloring table to {:?}: {}", path, err);
+        }
+        out
+    }
+
+    ///
+    /// Writes the (full) table to a file. The file starts with a header identifying the word list,
+    /// followed by each row in order.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write_cache_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(fs::File::create(path)?);
+        out.write_all(CACHE_FILE_MAGIC)?;
+        out.write_all(&(self.len() as u64).to_le_bytes())?;
+        out.write_all(&words_checksum(&self.words).to_le_bytes())?;
+        for guess_id in self.ids() {
+            out.write_all(self.row(guess_id))?;
+        }
+        out.flush()
+    }
+
+    ///
+    /// Reads a table written by write_cache_file. Returns Ok(None) if the file doesn't exist, or if
+    /// it was written for a different list of words.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_cache_file(
+        words: &[impl AsRef<str>],
+        path: impl AsRef<Path>,
+    ) -> io::Result<Option<Self>> {
+        let f = match fs::File::open(path) {
+            Ok(f) => f,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let mut input = BufReader::new(f);
+        let mut magic = [0u8; 4];
+        let mut n_words = [0u8; 8];
+        let mut checksum = [0u8; 8];
+        input.read_exact(&mut magic)?;
+        input.read_exact(&mut n_words)?;
+        input.read_exact(&mut checksum)?;
+
+        let out = Self::new(words);
+        if &magic != CACHE_FILE_MAGIC
+            || u64::from_le_bytes(n_words) != out.len() as u64
+            || u64::from_le_bytes(checksum) != words_checksum(&out.words)
+        {
+            return Ok(None);
+        }
+
+        for row in &out.rows {
+            let mut codes = vec![0; out.len()].into_boxed_slice();
+            input.read_exact(&mut codes)?;
+            // the table was only just created, so none of the rows could have been set yet
+            row.set(codes).expect("row should be empty");
+        }
+
+        Ok(Some(out))
+    }
+}
+
+///
+/// A stable hash (FNV-1a) of the words in a table, used to detect stale cache files
+///
+#[cfg(not(target_arch = "wasm32"))]
+fn words_checksum(words: &[String]) -> u64 {
+    words
+        .iter()
+        .flat_map(|word| word.bytes().chain(std::iter::once(b'\n')))
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 4] = ["latch", "batch", "chomp", "bloke"];
+
+    #[test]
+    fn test_table_matches_colorings() {
+        let table = ColoringTable::new(&WORDS);
+        for (guess_id, guess) in WORDS.iter().enumerate() {
+            for (answer_id, answer) in WORDS.iter().enumerate() {
+                assert_eq!(
+                    table.get(guess_id as WordId, answer_id as WordId),
+                    Colorings::with_guess_answer(guess, answer).to_code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cache_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("coloring_table_{}.bin", std::process::id()));
+        let table = ColoringTable::new(&WORDS);
+        table.write_cache_file(&path).unwrap();
+
+        let read = ColoringTable::read_cache_file(&WORDS, &path)
+            .unwrap()
+            .expect("words are the same");
+        for guess_id in table.ids() {
+            assert_eq!(read.row(guess_id), table.row(guess_id));
+        }
+
+        // a different word list should not use the cached table
+        let other_words = ["latch", "batch", "chomp", "bloks"];
+        assert!(ColoringTable::read_cache_file(&other_words, &path)
+            .unwrap()
+            .is_none());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,142 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{alphabet::*, color::*, prelude::*};
+use thiserror::Error;
+
+///
+/// Describes the rules of the particular wordle game being played: how many letters are in each
+/// word, how many guesses are allowed, and which letters the words are made of.
+///
+/// The default config is the original wordle (5 english letters, 6 turns), but many wordle clones
+/// use longer or shorter words, other languages, and some let you keep guessing until you find the
+/// answer.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GameConfig {
+    /// The number of letters in each word (MIN_WORD_SIZE to MAX_WORD_SIZE)
+    word_size: usize,
+    /// The number of guesses which can be made, or None if there is no limit
+    max_turns: Option<usize>,
+    /// The letters which words are made of
+    alphabet: Alphabet,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum GameConfigErr {
+    #[error(
+        "word size must be between {} and {}, got {0}",
+        MIN_WORD_SIZE,
+        MAX_WORD_SIZE
+    )]
+    UnsupportedWordSize(usize),
+    #[error("at least one turn must be allowed")]
+    NoTurns,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            word_size: WORD_SIZE,
+            max_turns: Some(NUM_TURNS),
+            alphabet: Alphabet::default(),
+        }
+    }
+}
+
+impl GameConfig {
+    /// Creates a config for words with word_size letters, allowing max_turns guesses (None = no limit)
+    pub fn new(word_size: usize, max_turns: Option<usize>) -> Result<Self, GameConfigErr> {
+        if !(MIN_WORD_SIZE..=MAX_WORD_SIZE).contains(&word_size) {
+            return Err(GameConfigErr::UnsupportedWordSize(word_size));
+        }
+
+        if max_turns == Some(0) {
+            return Err(GameConfigErr::NoTurns);
+        }
+
+        Ok(Self {
+            word_size,
+            max_turns,
+            alphabet: Alphabet::default(),
+        })
+    }
+
+    /// The same config, but for words made of letters from the alphabet
+    pub fn with_alphabet(self, alphabet: Alphabet) -> Self {
+        Self { alphabet, ..self }
+    }
+
+    /// The number of letters in each word
+    pub fn word_size(&self) -> usize {
+        self.word_size
+    }
+
+    /// The number of guesses which can be made, or None if there is no limit
+    pub fn max_turns(&self) -> Option<usize> {
+        self.max_turns
+    }
+
+    /// The letters which words are made of
+    pub fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
+    /// Whether or not the string is a wordle_str with the right number of letters for this game,
+    /// which only uses letters from the alphabet
+    pub fn is_word(&self, word: &str) -> bool {
+        num_letters(word) == self.word_size && self.alphabet.is_word(word)
+    }
+
+    /// The number of different Colorings which a guess can receive (see Colorings::num_states)
+    pub fn num_colorings(&self) -> usize {
+        Colorings::num_states(self.word_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_config_validation() {
+        let config = GameConfig::new(7, None).unwrap();
+        assert!(config.is_word("example"));
+        assert!(!config.is_word("sample"));
+        assert_eq!(config.num_colorings(), 2187);
+
+        let config = GameConfig::new(5, Some(6))
+            .unwrap()
+            .with_alphabet(Alphabet::SPANISH);
+        assert!(config.is_word("niños"));
+        assert!(!config.is_word("niñoss"));
+
+        assert_eq!(
+            GameConfig::new(3, Some(6)),
+            Err(GameConfigErr::UnsupportedWordSize(3))
+        );
+        assert_eq!(GameConfig::new(5, Some(0)), Err(GameConfigErr::NoTurns));
+        assert_eq!(GameConfig::new(5, Some(6)), Ok(GameConfig::default()));
+    }
+}
@@ -0,0 +1,368 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{alphabet::*, color::*, config::*, game::*, prelude::*};
+use std::fmt::{Display, Formatter};
+
+/// A set of letters, where bit n is set when the nth letter of the alphabet is in the set
+type LetterSet = u64;
+
+///
+/// Everything we know about the answer from the guesses made so far, in a form which can be
+/// checked against a word directly (instead of replaying each guess against each word).
+///
+/// Each guess tells us:
+///   * Correct letters are fixed at their position
+///   * Misplaced & Excluded letters cannot be at their position (if they were, they would have
+///     been colored Correct)
+///   * Every Correct/Misplaced letter appears in the answer, so the answer contains at least as
+///     many of each letter as the guess revealed (the minimum count)
+///   * When a letter is Excluded somewhere in the guess, the answer contains exactly as many of
+///     that letter as the guess revealed (usually zero, but not always with repeated letters)
+///
+/// Constraints are combined across guesses by keeping the strictest of each rule. Contradictory
+/// guesses produce Constraints which no word matches.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Constraints {
+    /// The letters which the answer is made of (letters are indexed by their position in it)
+    alphabet: Alphabet,
+    /// The number of letters in the answer (only that many positions are used below)
+    word_size: usize,
+    /// The letters which have been marked Correct at each position
+    fixed: [Option<char>; MAX_WORD_SIZE],
+    /// The letters which may still appear at each position
+    allowed: [LetterSet; MAX_WORD_SIZE],
+    /// The fewest number of times each letter must appear, indexed by Alphabet::index_of
+    min_counts: [usize; MAX_ALPHABET_SIZE],
+    /// The exact number of times each letter must appear (if known), indexed by Alphabet::index_of
+    exact_counts: [Option<usize>; MAX_ALPHABET_SIZE],
+}
+
+/// The default Constraints are for answers in the default GameConfig
+impl Default for Constraints {
+    fn default() -> Self {
+        Self::new(&GameConfig::default())
+    }
+}
+
+impl Constraints {
+    /// Constraints which any answer in the game matches (no guesses have been made)
+    pub fn new(config: &GameConfig) -> Self {
+        let alphabet = config.alphabet();
+        let all_letters = LetterSet::MAX >> (LetterSet::BITS as usize - alphabet.len());
+        Self {
+            alphabet,
+            word_size: config.word_size(),
+            fixed: [None; MAX_WORD_SIZE],
+            allowed: [all_letters; MAX_WORD_SIZE],
+            min_counts: [0; MAX_ALPHABET_SIZE],
+            exact_counts: [None; MAX_ALPHABET_SIZE],
+        }
+    }
+
+    /// Builds the Constraints which represent all the provided guesses (made in the game)
+    pub fn from_guesses<'g>(
+        config: &GameConfig,
+        guesses: impl IntoIterator<Item = &'g Guess>,
+    ) -> Self {
+        let mut out = Self::new(config);
+        for guess in guesses {
+            out.add_guess(guess);
+        }
+
+        out
+    }
+
+    /// Tightens these Constraints using the coloring of another guess
+    pub fn add_guess(&mut self, guess: &Guess) {
+        assert_eq!(guess.word.len(), self.word_size);
+        let mut revealed_counts = [0; MAX_ALPHABET_SIZE];
+        let mut has_excluded = [false; MAX_ALPHABET_SIZE];
+        for idx in 0..self.word_size {
+            let letter = guess.word[idx];
+            let letter_idx = self
+                .alphabet
+                .index_of(letter)
+                .expect("guesses only use letters of the alphabet");
+            let bit = 1 << letter_idx;
+            match guess.coloring[idx] {
+                Coloring::Correct => {
+                    self.fixed[idx] = Some(letter);
+                    self.allowed[idx] &= bit;
+                    revealed_counts[letter_idx] += 1;
+                }
+                Coloring::Misplaced => {
+                    self.allowed[idx] &= !bit;
+                    revealed_counts[letter_idx] += 1;
+                }
+                Coloring::Excluded => {
+                    self.allowed[idx] &= !bit;
+                    has_excluded[letter_idx] = true;
+                }
+            }
+        }
+
+        for letter in 0..self.alphabet.len() {
+            let revealed = revealed_counts[letter];
+            self.min_counts[letter] = self.min_counts[letter].max(revealed);
+            if has_excluded[letter] {
+                // two different exact counts can't both be right, so keeping the smaller one makes
+                // sure nothing matches (the larger count is already the minimum)
+                self.exact_counts[letter] = Some(match self.exact_counts[letter] {
+                    Some(existing) => existing.min(revealed),
+                    None => revealed,
+                });
+            }
+        }
+    }
+
+    /// Whether or not the word could be the answer, given these Constraints
+    pub fn matches(&self, word: &str) -> bool {
+        if num_letters(word) != self.word_size || !self.alphabet.is_word(word) {
+            return false;
+        }
+
+        if word
+            .chars()
+            .enumerate()
+            .any(|(idx, letter)| !self.is_allowed_at(idx, letter))
+        {
+            return false;
+        }
+
+        let counts = self.alphabet.count_letters(word);
+        (0..self.alphabet.len()).all(|letter| {
+            counts[letter] >= self.min_counts[letter]
+                && self.exact_counts[letter].is_none_or(|exact| counts[letter] == exact)
+        })
+    }
+
+    /// True when no guesses have narrowed down the answer at all
+    pub fn is_empty(&self) -> bool {
+        let config = GameConfig::new(self.word_size, None)
+            .expect("constraints always have a valid word size")
+            .with_alphabet(self.alphabet);
+        *self == Self::new(&config)
+    }
+
+    /// The number of letters in the answer
+    pub fn word_size(&self) -> usize {
+        self.word_size
+    }
+
+    /// The letter which was marked Correct at the position, if any
+    pub fn fixed_letter(&self, idx: usize) -> Option<char> {
+        self.fixed[idx]
+    }
+
+    /// Whether or not the letter may still appear at the position (letters which aren't in the
+    /// alphabet can't appear anywhere)
+    pub fn is_allowed_at(&self, idx: usize, letter: char) -> bool {
+        self.alphabet
+            .index_of(letter)
+            .is_some_and(|letter_idx| self.allowed[idx] & (1 << letter_idx) != 0)
+    }
+
+    /// The fewest number of times the letter must appear in the answer
+    pub fn min_count(&self, letter: char) -> usize {
+        self.alphabet
+            .index_of(letter)
+            .map_or(0, |letter_idx| self.min_counts[letter_idx])
+    }
+
+    /// The exact number of times the letter appears in the answer, if it is known
+    pub fn exact_count(&self, letter: char) -> Option<usize> {
+        match self.alphabet.index_of(letter) {
+            Some(letter_idx) => self.exact_counts[letter_idx],
+            None => Some(0),
+        }
+    }
+
+    /// Whether or not the letter is known to not appear in the answer at all
+    pub fn is_excluded(&self, letter: char) -> bool {
+        self.exact_count(letter) == Some(0)
+    }
+
+    ///
+    /// Describes these Constraints as a few short lines, such as:
+    ///
+    ///   pattern: _R__E
+    ///   contains: A, E, S×2
+    ///   at most: E×1
+    ///   excluded: N, O, T
+    ///   A not 1st, 3rd
+    ///
+    /// Positions are only listed for letters which might still appear in the answer, and
+    /// positions which are already fixed to a different letter are left out.
+    ///
+    pub fn summary(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        let fixed = &self.fixed[..self.word_size];
+        if fixed.iter().any(Option::is_some) {
+            let pattern: String = fixed
+                .iter()
+                .map(|c| c.map_or_else(|| "_".to_string(), uppercase))
+                .collect();
+            out.push(format!("pattern: {}", pattern));
+        }
+
+        let alphabet = || self.alphabet.letters().iter().copied();
+        let contains = join_letters(alphabet().filter(|c| self.min_count(*c) > 0), |c| {
+            self.min_count(c)
+        });
+        if !contains.is_empty() {
+            out.push(format!("contains: {}", contains));
+        }
+
+        let at_most = join_letters(
+            alphabet().filter(|c| !self.is_excluded(*c) && self.exact_count(*c).is_some()),
+            |c| self.exact_count(c).unwrap_or_default(),
+        );
+        if !at_most.is_empty() {
+            out.push(format!("at most: {}", at_most));
+        }
+
+        let excluded = join_letters(alphabet().filter(|c| self.is_excluded(*c)), |_| 1);
+        if !excluded.is_empty() {
+            out.push(format!("excluded: {}", excluded));
+        }
+
+        for letter in alphabet().filter(|c| !self.is_excluded(*c)) {
+            let positions: Vec<String> = (0..self.word_size)
+                .filter(|idx| self.fixed[*idx].is_none() && !self.is_allowed_at(*idx, letter))
+                .map(|idx| format!("{}{}", idx + 1, ordinal_suffix(idx + 1)))
+                .collect();
+            if !positions.is_empty() {
+                out.push(format!(
+                    "{} not {}",
+                    uppercase(letter),
+                    positions.join(", ")
+                ));
+            }
+        }
+
+        out
+    }
+}
+
+impl Display for Constraints {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.summary().join("; "))
+    }
+}
+
+/// The letter in uppercase, for display (some letters, like ß, become more than one character)
+fn uppercase(letter: char) -> String {
+    letter.to_uppercase().collect()
+}
+
+/// Formats letters as "A, B×2, C" where the count of each letter is only written when it isn't 1
+fn join_letters(letters: impl Iterator<Item = char>, count: impl Fn(char) -> usize) -> String {
+    letters
+        .map(|c| {
+            let letter = uppercase(c);
+            match count(c) {
+                1 => letter,
+                n => format!("{}×{}", letter, n),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordle::Coloring::*;
+
+    fn guess<const N: usize>(word: &str, coloring: [Coloring; N]) -> Guess {
+        Guess {
+            word: GuessWord::new(word),
+            coloring: coloring.into(),
+            expected_info: 0.0,
+            entropy_delta: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_constraints_match_colorings() {
+        let guesses = [
+            guess("abbey", [Correct, Correct, Excluded, Excluded, Excluded]),
+            guess("arose", [Correct, Excluded, Misplaced, Excluded, Excluded]),
+        ];
+        let constraints = Constraints::from_guesses(&GameConfig::default(), &guesses);
+
+        assert!(constraints.matches("ablow"));
+        // only one b is allowed
+        assert!(!constraints.matches("abbot"));
+        // the o must move
+        assert!(!constraints.matches("about"));
+        // e was excluded
+        assert!(!constraints.matches("abloe"));
+        // a word matches exactly when it would have produced the same colorings as the guesses
+        for answer in ["ablow", "abbot", "about", "abloe", "allow", "abyss"] {
+            let expected = guesses
+                .iter()
+                .all(|g| Colorings::with_guess_answer(g.word.as_str(), answer) == g.coloring);
+            assert_eq!(constraints.matches(answer), expected, "{}", answer);
+        }
+    }
+
+    #[test]
+    fn test_constraints_summary() {
+        let constraints = Constraints::from_guesses(
+            &GameConfig::default(),
+            &[guess(
+                "sassy",
+                [Misplaced, Correct, Excluded, Correct, Excluded],
+            )],
+        );
+
+        assert_eq!(
+            constraints.summary(),
+            vec![
+                "pattern: _A_S_",
+                "contains: A, S×2",
+                "at most: S×2",
+                "excluded: Y",
+                "S not 1st, 3rd",
+            ]
+        );
+        assert!(Constraints::default().is_empty());
+        assert!(Constraints::default().summary().is_empty());
+
+        let constraints = Constraints::from_guesses(
+            &GameConfig::new(6, None).unwrap(),
+            &[guess(
+                "kitten",
+                [Excluded, Correct, Correct, Correct, Correct, Correct],
+            )],
+        );
+        assert_eq!(constraints.summary()[0], "pattern: _ITTEN");
+        assert!(constraints.matches("mitten"));
+        assert!(!constraints.matches("kitten"));
+        assert!(!constraints.matches("itten"));
+    }
+}
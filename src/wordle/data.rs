@@ -22,24 +22,145 @@
  * SOFTWARE.
  */
 
-use std::borrow::Cow;
-use std::mem;
-use crate::wordle::prelude::*;
+use crate::wordle::{
+    alphabet::*, config::*, guess_model::*, morphology::*, past_answers::*, prelude::*, prior::*,
+};
 use lazy_static::lazy_static;
 use rust_embed::RustEmbed;
-use std::num::ParseFloatError;
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use std::mem;
+use std::num::{ParseFloatError, ParseIntError};
 use std::str::Utf8Error;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{collections::HashSet, path::Path};
 use thiserror::Error;
 
 // Stores "input data" which is manually updated/configured
 pub const DATA_DIRECTORY: &str = "data/";
-pub const FREQUENCY_FILE_NAME: &str = "5word_frequencies.txt";
 pub const ALLOWED_WORDS_FILE_NAME: &str = "allowed_words.txt";
+pub const ANSWERS_FILE_NAME: &str = "answers.txt";
+pub const PAST_ANSWERS_FILE_NAME: &str = "past_answers.txt";
+/// Common (english) words which are shorter than the allowed words, with one word per line. These
+/// are the base forms of most plurals & past tenses (see Inflection), like "book" for "books".
+pub const BASE_WORDS_FILE_NAME: &str = "base_words.txt";
 
 // Stores "derived data" which is generated at build time using the data from the text-files above
 pub const EMBED_DATA_DIRECTORY: &str = "txt_data/";
-pub const DEFAULT_STATE_DATA_FILE_NAME: &str = "default_state_data.txt";
-pub const ORDERED_ALLOWED_WORDS_FILE_NAME: &str = "allowed_words_ord.bin";
+pub const GUESS_COUNT_MODEL_FILE_NAME: &str = "guess_count_model.txt";
+
+// Most of the data depends on the size of the words, so those file names include the word size.
+// Words in other languages than english also have the name of their alphabet in the file names.
+
+/// The allowed words for a game using the alphabet (like allowed_words_spanish.txt)
+pub fn allowed_words_file_name(alphabet: &Alphabet) -> String {
+    match alphabet_suffix(alphabet) {
+        suffix if suffix.is_empty() => ALLOWED_WORDS_FILE_NAME.to_string(),
+        suffix => format!("allowed_words{}.txt", suffix),
+    }
+}
+
+/// The (optional) list of words which can be the answer for a game using the alphabet (like
+/// answers.txt). Every answer can also be guessed, even if it's missing from the allowed words.
+pub fn answers_file_name(alphabet: &Alphabet) -> String {
+    match alphabet_suffix(alphabet) {
+        suffix if suffix.is_empty() => ANSWERS_FILE_NAME.to_string(),
+        suffix => format!("answers{}.txt", suffix),
+    }
+}
+
+/// The (optional) dated answers of past daily puzzles for a game using the alphabet (like
+/// past_answers.txt). Each line has a date (like 2021-06-19) and the word, split by a space.
+pub fn past_answers_file_name(alphabet: &Alphabet) -> String {
+    match alphabet_suffix(alphabet) {
+        suffix if suffix.is_empty() => PAST_ANSWERS_FILE_NAME.to_string(),
+        suffix => format!("past_answers{}.txt", suffix),
+    }
+}
+
+/// The word frequency data for words in the game (like 5word_frequencies.txt)
+pub fn frequency_file_name(config: &GameConfig) -> String {
+    format!(
+        "{}word_frequencies{}.txt",
+        config.word_size(),
+        alphabet_suffix(&config.alphabet())
+    )
+}
+
+/// The cached default state data for words in the game
+pub fn default_state_data_file_name(config: &GameConfig) -> String {
+    format!(
+        "default_state_data_{}{}.txt",
+        config.word_size(),
+        alphabet_suffix(&config.alphabet())
+    )
+}
+
+/// The cached default state data for when no past answers can repeat (see RepeatPolicy), only
+/// generated when there is a past answers file
+pub fn no_repeats_default_state_data_file_name(config: &GameConfig) -> String {
+    format!(
+        "default_state_data_{}{}_no_repeats.txt",
+        config.word_size(),
+        alphabet_suffix(&config.alphabet())
+    )
+}
+
+/// The RankSigmoidPrior fitted to the past answers of the game (by gen_word_prior)
+pub fn word_prior_file_name(config: &GameConfig) -> String {
+    format!(
+        "word_prior_{}{}.txt",
+        config.word_size(),
+        alphabet_suffix(&config.alphabet())
+    )
+}
+
+/// The (compressed) allowed words in the game, in rank order
+pub fn ordered_allowed_words_file_name(config: &GameConfig) -> String {
+    format!(
+        "allowed_words_ord_{}{}.bin",
+        config.word_size(),
+        alphabet_suffix(&config.alphabet())
+    )
+}
+
+/// The (compressed) answers in the game, only generated when there is an answers file
+pub fn compressed_answers_file_name(config: &GameConfig) -> String {
+    format!(
+        "answers_{}{}.bin",
+        config.word_size(),
+        alphabet_suffix(&config.alphabet())
+    )
+}
+
+/// The dated past answers in the game, each stored as an AnswerDate followed by a CompressedWord.
+/// Only generated when there is a past answers file.
+pub fn compressed_past_answers_file_name(config: &GameConfig) -> String {
+    format!(
+        "past_answers_{}{}.bin",
+        config.word_size(),
+        alphabet_suffix(&config.alphabet())
+    )
+}
+
+/// The allowed words which are inflections of other words (see Inflection), each stored as a
+/// CompressedWord followed by Inflection::to_byte
+pub fn compressed_inflections_file_name(config: &GameConfig) -> String {
+    format!(
+        "inflections_{}{}.bin",
+        config.word_size(),
+        alphabet_suffix(&config.alphabet())
+    )
+}
+
+/// Nothing for the default (english) alphabet, otherwise "_" and the name of the alphabet
+fn alphabet_suffix(alphabet: &Alphabet) -> String {
+    if *alphabet == Alphabet::default() {
+        String::new()
+    } else {
+        format!("_{}", alphabet.name())
+    }
+}
 
 lazy_static! {
     pub static ref DATA: Data = Data::read().expect("should have no failures reading data...");
@@ -50,174 +171,604 @@ lazy_static! {
 #[exclude = ".*"]
 struct RawData;
 
-const COMPRESSED_SIZE_BITS: usize = (ALPHABET_SIZE as u64).pow(WORD_SIZE as _).ilog2() as usize;
-pub const COMPRESSED_SIZE: usize = (COMPRESSED_SIZE_BITS + 7) / 8;
+/// The number of bytes needed to store a CompressedWord with word_size letters from the alphabet
+pub fn compressed_size(alphabet: &Alphabet, word_size: usize) -> usize {
+    let max_value = (alphabet.len() as u64).pow(word_size as u32) - 1;
+    (max_value.ilog2() as usize + 1).div_ceil(8)
+}
 
+///
+/// A word stored as a number, where each letter is a digit (its index in the alphabet). With a
+/// MAX_ALPHABET_SIZE of 64 and at most MAX_WORD_SIZE letters, every word fits in a u64.
+///
 #[derive(Copy, Clone)]
-pub struct CompressedWord([u8; COMPRESSED_SIZE]);
+pub struct CompressedWord {
+    value: u64,
+    alphabet: Alphabet,
+    word_size: usize,
+}
 
 impl CompressedWord {
-    pub fn new(s: &str) -> Self {
-        assert!(is_wordle_str(s));
+    /// The word must only use letters of the alphabet (see Alphabet::is_word)
+    pub fn new(alphabet: Alphabet, s: &str) -> Self {
+        assert!(alphabet.is_word(s), "{:?} is not a {} word", s, alphabet);
+        let base = alphabet.len() as u64;
         let mut x = 0;
 
-        for ch in s.bytes().rev() {
-            x *= ALPHABET_SIZE as u64;
-            x += ch as u64 - b'a' as u64;
+        for ch in s.chars().rev() {
+            x *= base;
+            x += alphabet.index_of(ch).expect("checked that it is a word") as u64;
         }
 
-        let bytes = x.to_le_bytes();
-        let (important, unimportant) = bytes.split_at(COMPRESSED_SIZE);
-        debug_assert!(unimportant.iter().all(|&b| b == 0));
-        let mut result = [0; COMPRESSED_SIZE];
-        result.copy_from_slice(important);
-
-        Self(result)
+        Self {
+            value: x,
+            alphabet,
+            word_size: num_letters(s),
+        }
     }
 
-    pub fn as_bytes(self) -> [u8; COMPRESSED_SIZE] {
-        self.0
+    /// Reads a word with word_size letters from the output of as_bytes
+    pub fn from_bytes(bytes: &[u8], alphabet: Alphabet, word_size: usize) -> Self {
+        let mut x = [0; mem::size_of::<u64>()];
+        x[..bytes.len()].copy_from_slice(bytes);
+        Self {
+            value: u64::from_le_bytes(x),
+            alphabet,
+            word_size,
+        }
     }
 
-    pub fn to_string(self) -> String {
-        let mut res = String::with_capacity(WORD_SIZE);
+    pub fn as_bytes(self) -> Vec<u8> {
+        let bytes = self.value.to_le_bytes();
+        let (important, unimportant) =
+            bytes.split_at(compressed_size(&self.alphabet, self.word_size));
+        debug_assert!(unimportant.iter().all(|&b| b == 0));
+        important.to_vec()
+    }
+}
 
-        let mut x = [0; mem::size_of::<u64>()];
-        x[..COMPRESSED_SIZE].copy_from_slice(&self.0);
-        let mut x = u64::from_le_bytes(x);
-        for _ in 0..WORD_SIZE {
-            let ch = (x % ALPHABET_SIZE as u64) as u8 + b'a';
-            x /= ALPHABET_SIZE as u64;
-            res.push(ch as char);
+impl fmt::Display for CompressedWord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let base = self.alphabet.len() as u64;
+        let mut x = self.value;
+        for _ in 0..self.word_size {
+            f.write_char(self.alphabet.letter((x % base) as usize))?;
+            x /= base;
         }
-        res
+        Ok(())
     }
 }
 
 /// Holds all of the data represented by the static/embedded text files
 #[derive(Clone, Debug)]
 pub struct Data {
+    /// The number of letters in every word
+    pub word_size: usize,
+    /// The letters which every word is made of
+    pub alphabet: Alphabet,
     /// The list of words which can be guessed, in rank order from most common to least common
     pub allowed_words: Vec<String>,
+    /// The words which can be the answer (a subset of allowed_words), if there is an answer list.
+    /// Without one, every allowed word could be the answer.
+    pub answers: Option<Vec<String>>,
+    /// The answers of past daily puzzles (all of them allowed words), from oldest to newest
+    pub past_answers: Vec<PastAnswer>,
+    /// The allowed words which look like plurals or past tenses of other words (see Inflection)
+    pub inflections: Vec<InflectedWord>,
     /// Cached calculation of scored guesses in the "default state" (see game.rs for more details)
     pub default_state_data: Option<Vec<DefaultStateEntry>>,
+    /// Like default_state_data, but for when none of the past_answers can be the answer again
+    pub no_repeats_default_state_data: Option<Vec<DefaultStateEntry>>,
+    /// The fitted GuessCountModel (see guess_model.rs), if it was generated
+    pub guess_count_model: Option<GuessCountModel>,
+    /// The RankSigmoidPrior fitted to the past answers (see gen_word_prior), if it was generated
+    pub word_prior: Option<RankSigmoidPrior>,
 }
 
 #[derive(Clone, Debug)]
 pub struct DefaultStateEntry {
+    /// The id of the ScoringStrategy which produced the score
+    pub strategy: String,
     /// The word being suggested
     pub word: String,
     /// The total score (score.abs)
     pub score: WordleFloat,
+    /// The tie breaker for the score (score.tie_break)
+    pub tie_break: WordleFloat,
     /// The expected_info for this guess (score.expected_info)
     pub expected_info: WordleFloat,
     /// The weight calculated for this guess (score.weight)
     pub weight: WordleFloat,
+    /// The size of the largest coloring bucket for this guess (score.worst_case)
+    pub worst_case: usize,
 }
 
 #[derive(Error, Debug)]
 pub enum LoadDataErr {
     #[error("missing allowed words file")]
     MissingAllowedWordsFile,
+    #[error("couldn't read {0}")]
+    Io(String, #[source] std::io::Error),
     #[error(transparent)]
     EncodingError(#[from] Utf8Error),
-    #[error("malformed default data line '{0}'")]
-    BadDefaultDataLine(String),
+    #[error("malformed guess count model '{0}'")]
+    BadGuessCountModel(String),
     #[error("malformed floating point text '{0}'")]
     BadFloatStr(String, #[source] ParseFloatError),
-    #[error("the word '{0}' is not a valid wordle word")]
+    #[error("malformed past answers file")]
+    BadPastAnswers(#[source] AnswerDateErr),
+    #[error("unknown inflection {0}")]
+    BadInflection(u8),
+    #[error("{file} line {line}: {reason}")]
+    BadEntry {
+        file: String,
+        /// The line number of the entry, starting from 1
+        line: usize,
+        reason: EntryErr,
+    },
+}
+
+///
+/// Why a line of a data file couldn't be used (see LoadDataErr::BadEntry)
+///
+#[derive(Error, Debug)]
+pub enum EntryErr {
+    #[error("malformed line '{0}'")]
+    Malformed(String),
+    #[error("malformed floating point text '{0}'")]
+    BadFloatStr(String, #[source] ParseFloatError),
+    #[error("malformed integer text '{0}'")]
+    BadIntStr(String, #[source] ParseIntError),
+    #[error("the word '{0}' is not a valid word for this game")]
     NonWordleWord(String),
+    #[error("the word '{0}' is listed more than once")]
+    DuplicateWord(String),
+    #[error(transparent)]
+    BadDate(#[from] AnswerDateErr),
 }
 
 impl Data {
+    /// Reads the data for the default GameConfig (5 letter words)
     pub fn read() -> Result<Self, LoadDataErr> {
+        Self::read_with_config(&GameConfig::default())
+    }
+
+    /// Reads the data for words of the size (and alphabet) used by the config
+    pub fn read_with_config(config: &GameConfig) -> Result<Self, LoadDataErr> {
         let out = Self {
-            allowed_words: try_read_allowed_words()?,
-            default_state_data: try_read_default_state_data()?,
+            word_size: config.word_size(),
+            alphabet: config.alphabet(),
+            allowed_words: try_read_allowed_words(config)?,
+            answers: try_read_answers(config)?,
+            past_answers: try_read_past_answers(config)?,
+            inflections: try_read_inflections(config)?,
+            default_state_data: try_read_default_state_data(
+                config,
+                &default_state_data_file_name(config),
+            )?,
+            no_repeats_default_state_data: try_read_default_state_data(
+                config,
+                &no_repeats_default_state_data_file_name(config),
+            )?,
+            guess_count_model: try_read_guess_count_model()?,
+            word_prior: try_read_word_prior(config)?,
         };
         log::debug!(
             "got {} allowed words from data file",
             out.allowed_words.len()
         );
+        if let Some(answers) = &out.answers {
+            log::debug!("got {} answers from data file", answers.len());
+        }
+        if !out.past_answers.is_empty() {
+            log::debug!("got {} past answers", out.past_answers.len());
+        }
+        if !out.inflections.is_empty() {
+            log::debug!("got {} inflected words", out.inflections.len());
+        }
         if let Some(default_state) = &out.default_state_data {
             log::debug!("got {} default items", default_state.len());
         }
         Ok(out)
     }
+
+    ///
+    /// Reads the data from (uncompressed) text files instead of the embedded data, so that games
+    /// with their own dictionaries can be solved without rebuilding. The files have the same format
+    /// as the files in the data directory:
+    ///
+    /// * allowed_words has one word per line, from most common to least common
+    /// * answers (optional) has one word per line, see Data::answers
+    /// * past_answers (optional) has a date and a word on each line, see Data::past_answers
+    ///
+    /// Words with a different number of letters than the config's word size are skipped (so one
+    /// file can hold the words for every word size), but any other bad line is an error. There is
+    /// no cached default state data, so the first recommendations take a while to compute. The
+    /// inflections are found among the allowed words.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_paths(
+        config: &GameConfig,
+        allowed_words: &Path,
+        answers: Option<&Path>,
+        past_answers: Option<&Path>,
+    ) -> Result<Self, LoadDataErr> {
+        let mut allowed_words = read_word_list(config, allowed_words)?;
+        let answers = answers
+            .map(|path| read_word_list(config, path))
+            .transpose()?;
+        let past_answers = past_answers
+            .map(|path| read_past_answers(config, path))
+            .transpose()?
+            .unwrap_or_default();
+
+        // every answer must be guessable, even if the allowed words file forgot about it
+        let mut allowed: HashSet<String> = allowed_words.iter().cloned().collect();
+        for answer in answers.iter().flatten() {
+            if allowed.insert(answer.clone()) {
+                allowed_words.push(answer.clone());
+            }
+        }
+        let inflections = find_inflections(
+            &config.alphabet(),
+            allowed_words.iter().map(String::as_str),
+            |word| allowed.contains(word),
+        );
+
+        Ok(Self {
+            word_size: config.word_size(),
+            alphabet: config.alphabet(),
+            allowed_words,
+            answers,
+            past_answers,
+            inflections,
+            default_state_data: None,
+            no_repeats_default_state_data: None,
+            guess_count_model: None,
+            word_prior: None,
+        })
+    }
+}
+
+/// Reads a text file with one word per line (see Data::from_paths)
+#[cfg(not(target_arch = "wasm32"))]
+fn read_word_list(config: &GameConfig, path: &Path) -> Result<Vec<String>, LoadDataErr> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for_each_entry(path, |line| {
+        let word = match parse_entry_word(config, line)? {
+            Some(word) => word,
+            None => return Ok(()),
+        };
+        if !seen.insert(word.clone()) {
+            return Err(EntryErr::DuplicateWord(word));
+        }
+        out.push(word);
+        Ok(())
+    })?;
+    Ok(out)
 }
 
-/// Reads the allowed words text file. This is pretty simple: one allowed word per line.
-fn try_read_allowed_words() -> Result<Vec<String>, LoadDataErr> {
-    Ok(retrieve_file_as_bytes(ORDERED_ALLOWED_WORDS_FILE_NAME)?
-        .ok_or(LoadDataErr::MissingAllowedWordsFile)?
-        .chunks(COMPRESSED_SIZE)
-        .map(|b| CompressedWord(b.try_into().unwrap()).to_string())
-        .collect())
+///
+/// Reads a word frequency file (like 5word_frequencies.txt), which has a word and the number of
+/// times it was seen on each line. The counts can be used by a WordCountPrior.
+///
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_word_counts(
+    config: &GameConfig,
+    path: &Path,
+) -> Result<Vec<(String, WordleFloat)>, LoadDataErr> {
+    let mut out = Vec::new();
+    for_each_entry(path, |line| {
+        let (word, count) = line
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| EntryErr::Malformed(line.to_string()))?;
+        let count = count
+            .trim()
+            .parse::<WordleFloat>()
+            .map_err(|err| EntryErr::BadFloatStr(count.to_string(), err))?;
+        if let Some(word) = parse_entry_word(config, word)? {
+            out.push((word, count));
+        }
+        Ok(())
+    })?;
+    Ok(out)
 }
 
-/// Reads cached default state data, optionally (if it exists)
-fn try_read_default_state_data() -> Result<Option<Vec<DefaultStateEntry>>, LoadDataErr> {
+/// Reads a text file with a date and a word on each line (see Data::from_paths). The past answers
+/// are sorted from oldest to newest.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_past_answers(config: &GameConfig, path: &Path) -> Result<Vec<PastAnswer>, LoadDataErr> {
+    let mut out = Vec::new();
+    for_each_entry(path, |line| {
+        let (date, word) = line
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| EntryErr::Malformed(line.to_string()))?;
+        let date = date.parse()?;
+        if let Some(word) = parse_entry_word(config, word)? {
+            out.push(PastAnswer { date, word });
+        }
+        Ok(())
+    })?;
+    out.sort_by_key(|past| past.date);
+    Ok(out)
+}
+
+/// Calls f with every non-empty line of the file, adding the file & line number to any error
+#[cfg(not(target_arch = "wasm32"))]
+fn for_each_entry(
+    path: &Path,
+    mut f: impl FnMut(&str) -> Result<(), EntryErr>,
+) -> Result<(), LoadDataErr> {
+    let file = path.display().to_string();
+    let raw_data =
+        std::fs::read_to_string(path).map_err(|err| LoadDataErr::Io(file.clone(), err))?;
+    for (idx, line) in raw_data.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        f(line).map_err(|reason| LoadDataErr::BadEntry {
+            file: file.clone(),
+            line: idx + 1,
+            reason,
+        })?;
+    }
+    Ok(())
+}
+
+///
+/// Reads the word on a line (which must be the only thing on it), returning None when it has the
+/// wrong number of letters for the game
+///
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_entry_word(config: &GameConfig, line: &str) -> Result<Option<String>, EntryErr> {
+    let mut parts = line.split_whitespace();
+    let word = match (parts.next(), parts.next()) {
+        (Some(word), None) => config.alphabet().normalize(word),
+        _ => return Err(EntryErr::Malformed(line.to_string())),
+    };
+
+    if num_letters(&word) != config.word_size() {
+        Ok(None)
+    } else if config.is_word(&word) {
+        Ok(Some(word))
+    } else {
+        Err(EntryErr::NonWordleWord(word))
+    }
+}
+
+/// Reads the allowed words file. This is pretty simple: one CompressedWord after another.
+fn try_read_allowed_words(config: &GameConfig) -> Result<Vec<String>, LoadDataErr> {
+    Ok(decompress_words(
+        config,
+        &retrieve_file_as_bytes(&ordered_allowed_words_file_name(config))?
+            .ok_or(LoadDataErr::MissingAllowedWordsFile)?,
+    ))
+}
+
+/// Reads the answers file, optionally (if it exists). It has the same format as the allowed words.
+fn try_read_answers(config: &GameConfig) -> Result<Option<Vec<String>>, LoadDataErr> {
+    Ok(
+        retrieve_file_as_bytes(&compressed_answers_file_name(config))?
+            .map(|bytes| decompress_words(config, &bytes)),
+    )
+}
+
+/// Reads the past answers file, optionally (if it exists, otherwise there are no past answers)
+fn try_read_past_answers(config: &GameConfig) -> Result<Vec<PastAnswer>, LoadDataErr> {
+    let (alphabet, word_size) = (config.alphabet(), config.word_size());
+    let bytes = match retrieve_file_as_bytes(&compressed_past_answers_file_name(config))? {
+        Some(bytes) => bytes,
+        None => return Ok(Vec::new()),
+    };
+
+    bytes
+        .chunks(AnswerDate::NUM_BYTES + compressed_size(&alphabet, word_size))
+        .map(|chunk| {
+            let (date, word) = chunk.split_at(AnswerDate::NUM_BYTES);
+            let date = AnswerDate::from_bytes(date.try_into().expect("split at NUM_BYTES"))
+                .map_err(LoadDataErr::BadPastAnswers)?;
+            Ok(PastAnswer {
+                date,
+                word: CompressedWord::from_bytes(word, alphabet, word_size).to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Reads the inflections file, optionally (if it exists, otherwise no words are inflections)
+fn try_read_inflections(config: &GameConfig) -> Result<Vec<InflectedWord>, LoadDataErr> {
+    let (alphabet, word_size) = (config.alphabet(), config.word_size());
+    let bytes = match retrieve_file_as_bytes(&compressed_inflections_file_name(config))? {
+        Some(bytes) => bytes,
+        None => return Ok(Vec::new()),
+    };
+
+    bytes
+        .chunks(compressed_size(&alphabet, word_size) + 1)
+        .map(|chunk| {
+            let (word, inflection) = chunk.split_at(chunk.len() - 1);
+            Ok(InflectedWord {
+                word: CompressedWord::from_bytes(word, alphabet, word_size).to_string(),
+                inflection: Inflection::from_byte(inflection[0])
+                    .ok_or(LoadDataErr::BadInflection(inflection[0]))?,
+            })
+        })
+        .collect()
+}
+
+fn decompress_words(config: &GameConfig, bytes: &[u8]) -> Vec<String> {
+    let (alphabet, word_size) = (config.alphabet(), config.word_size());
+    bytes
+        .chunks(compressed_size(&alphabet, word_size))
+        .map(|b| CompressedWord::from_bytes(b, alphabet, word_size).to_string())
+        .collect()
+}
+
+/// Reads the fitted GuessCountModel, optionally (if it exists). The file contains the 3 coefficients
+/// of the model on a single line, split by a space.
+fn try_read_guess_count_model() -> Result<Option<GuessCountModel>, LoadDataErr> {
+    let raw_data = match retrieve_file_as_str(GUESS_COUNT_MODEL_FILE_NAME)? {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+
+    let mut coefficients = [0.0; 3];
+    let mut parts = raw_data.split_whitespace();
+    for coefficient in &mut coefficients {
+        let raw = parts
+            .next()
+            .ok_or_else(|| LoadDataErr::BadGuessCountModel(raw_data.to_string()))?;
+        *coefficient = raw
+            .parse::<WordleFloat>()
+            .map_err(|err| LoadDataErr::BadFloatStr(raw.to_string(), err))?;
+    }
+
+    if parts.next().is_some() {
+        return Err(LoadDataErr::BadGuessCountModel(raw_data.to_string()));
+    }
+
+    Ok(Some(GuessCountModel { coefficients }))
+}
+
+/// Reads the fitted RankSigmoidPrior, optionally (if it exists). The file contains n_common and
+/// width on a single line, split by a space.
+fn try_read_word_prior(config: &GameConfig) -> Result<Option<RankSigmoidPrior>, LoadDataErr> {
+    let file_name = word_prior_file_name(config);
+    let raw_data = match retrieve_file_as_str(&file_name)? {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+
+    let bad_entry = |reason| LoadDataErr::BadEntry {
+        file: file_name.clone(),
+        line: 1,
+        reason,
+    };
+    let mut parameters = [0.0; 2];
+    let mut parts = raw_data.split_whitespace();
+    for parameter in &mut parameters {
+        let raw = parts
+            .next()
+            .ok_or_else(|| bad_entry(EntryErr::Malformed(raw_data.to_string())))?;
+        *parameter = raw
+            .parse::<WordleFloat>()
+            .map_err(|err| bad_entry(EntryErr::BadFloatStr(raw.to_string(), err)))?;
+    }
+
+    if parts.next().is_some() {
+        return Err(bad_entry(EntryErr::Malformed(raw_data.to_string())));
+    }
+
+    let [n_common, width] = parameters;
+    Ok(Some(RankSigmoidPrior { n_common, width }))
+}
+
+/// Reads cached default state data from the file, optionally (if it exists)
+fn try_read_default_state_data(
+    config: &GameConfig,
+    file_name: &str,
+) -> Result<Option<Vec<DefaultStateEntry>>, LoadDataErr> {
     // try to open the default state data (if it doesn't exist, then just return Ok(None))
-    let raw_data = match retrieve_file_as_str(DEFAULT_STATE_DATA_FILE_NAME)? {
+    let raw_data = match retrieve_file_as_str(file_name)? {
         Some(data) => data,
         None => return Ok(None),
     };
 
-    let mut out = Vec::with_capacity(N_RECOMMENDATIONS);
+    let mut out = Vec::new();
     // parse each line in default_state_data
-    for line in raw_data.lines() {
-        // this file is expected to contain 4 pieces of information on each line, split by a space:
-        //
-        // * word being suggested (5 letter string / wordle word)
-        // * it's score (float)
-        // * it's expected_info (float)
-        // * it's weight (float)
-        //
-        // The file should also be already sorted from highest -> lowest score
-        //
-        let mut parts = line.splitn(4, ' ');
-
-        // read the word
-        let word = if let Some(w) = parts.next() {
-            normalize_wordle_word(w)
-        } else {
-            continue;
-        };
+    for (idx, line) in raw_data.lines().enumerate() {
+        let entry =
+            parse_default_state_line(config, line).map_err(|reason| LoadDataErr::BadEntry {
+                file: file_name.to_string(),
+                line: idx + 1,
+                reason,
+            })?;
+        out.extend(entry);
+    }
 
-        // validate
-        if !is_wordle_str(&word) {
-            return Err(LoadDataErr::NonWordleWord(word));
-        }
-
-        // helper closure to "consume" a float
-        // basically reads whatever parts.next() returns as a float, returning an error if the float
-        // isn't valid, or doesn't exist
-        let mut consume_float = || {
-            // first get the string representation & handle the case when it doesn't exist
-            let raw = parts
-                .next()
-                .ok_or_else(|| LoadDataErr::BadDefaultDataLine(line.to_string()))?;
-
-            // then try to parse it as a WordleFloat (aka f32/f64), and wrap the error if it can't
-            // be parsed
-            raw.trim()
-                .parse::<WordleFloat>()
-                .map_err(|err| LoadDataErr::BadFloatStr(raw.to_string(), err))
-        };
+    Ok(Some(out))
+}
+
+/// Parses a line of the default state data, returning None for an empty line
+fn parse_default_state_line(
+    config: &GameConfig,
+    line: &str,
+) -> Result<Option<DefaultStateEntry>, EntryErr> {
+    // this file is expected to contain 7 pieces of information on each line, split by a space:
+    //
+    // * id of the scoring strategy (string)
+    // * word being suggested (wordle word with the config's word size)
+    // * it's score (float)
+    // * it's tie_break (float)
+    // * it's expected_info (float)
+    // * it's weight (float)
+    // * it's worst_case (integer)
+    //
+    // The lines for each strategy should also be already sorted from highest -> lowest score
+    //
+    let mut parts = line.splitn(7, ' ');
+
+    // read the strategy id
+    let strategy = match parts.next() {
+        Some(s) if !s.is_empty() => s.to_string(),
+        _ => return Ok(None),
+    };
+
+    // read the word
+    let word = parts
+        .next()
+        .map(|word| config.alphabet().normalize(word))
+        .ok_or_else(|| EntryErr::Malformed(line.to_string()))?;
 
-        // consume the three floats (score, expected_info, weight)
-        let score = consume_float()?;
-        let expected_info = consume_float()?;
-        let weight = consume_float()?;
-        out.push(DefaultStateEntry {
-            word,
-            score,
-            expected_info,
-            weight,
-        });
+    // validate
+    if !config.is_word(&word) {
+        return Err(EntryErr::NonWordleWord(word));
     }
 
-    Ok(Some(out))
+    // helper closure to "consume" a float
+    // basically reads whatever parts.next() returns as a float, returning an error if the float
+    // isn't valid, or doesn't exist
+    let mut consume_float = || {
+        // first get the string representation & handle the case when it doesn't exist
+        let raw = parts
+            .next()
+            .ok_or_else(|| EntryErr::Malformed(line.to_string()))?;
+
+        // then try to parse it as a WordleFloat (aka f32/f64), and wrap the error if it can't
+        // be parsed
+        raw.trim()
+            .parse::<WordleFloat>()
+            .map_err(|err| EntryErr::BadFloatStr(raw.to_string(), err))
+    };
+
+    // consume the four floats (score, tie_break, expected_info, weight)
+    let score = consume_float()?;
+    let tie_break = consume_float()?;
+    let expected_info = consume_float()?;
+    let weight = consume_float()?;
+
+    // and finally the worst_case
+    let raw_worst_case = parts
+        .next()
+        .ok_or_else(|| EntryErr::Malformed(line.to_string()))?;
+    let worst_case = raw_worst_case
+        .trim()
+        .parse::<usize>()
+        .map_err(|err| EntryErr::BadIntStr(raw_worst_case.to_string(), err))?;
+
+    Ok(Some(DefaultStateEntry {
+        strategy,
+        word,
+        score,
+        tie_break,
+        expected_info,
+        weight,
+        worst_case,
+    }))
 }
 
 fn retrieve_file_as_bytes(name: &str) -> Result<Option<Cow<'static, [u8]>>, LoadDataErr> {
@@ -239,8 +790,83 @@ fn retrieve_file_as_str(name: &str) -> Result<Option<Cow<'static, str>>, LoadDat
     retrieve_file_as_bytes(name).and_then(|bytes| -> Result<_, LoadDataErr> {
         match bytes {
             Some(Cow::Borrowed(b)) => Ok(Some(Cow::Borrowed(std::str::from_utf8(b)?))),
-            Some(Cow::Owned(v)) => Ok(Some(Cow::Owned(String::from_utf8(v).map_err(|e| e.utf8_error())?))),
-            None => Ok(None)
+            Some(Cow::Owned(v)) => Ok(Some(Cow::Owned(
+                String::from_utf8(v).map_err(|e| e.utf8_error())?,
+            ))),
+            None => Ok(None),
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compressed_word_round_trip() {
+        for (alphabet, word) in [
+            (Alphabet::ENGLISH, "crane"),
+            (Alphabet::SPANISH, "niños"),
+            (Alphabet::GERMAN, "größe"),
+            (Alphabet::NERDLE, "12+35=47"),
+        ] {
+            let bytes = CompressedWord::new(alphabet, word).as_bytes();
+            assert_eq!(bytes.len(), compressed_size(&alphabet, num_letters(word)));
+
+            let decoded = CompressedWord::from_bytes(&bytes, alphabet, num_letters(word));
+            assert_eq!(decoded.to_string(), word);
+        }
+
+        // the english format is unchanged, so the existing data files still work
+        assert_eq!(compressed_size(&Alphabet::ENGLISH, 5), 3);
+    }
+
+    #[test]
+    fn test_data_from_paths() {
+        let dir = std::env::temp_dir().join(format!("wordle_data_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+
+        let allowed = write("allowed.txt", "crane\nslate\nlonger\n\nCRATE\n");
+        let answers = write("answers.txt", "crate\ntrace\n");
+        let past_answers = write("past_answers.txt", "2022-01-02 trace\n2022-01-01 crate\n");
+        let config = GameConfig::default();
+        let data =
+            Data::from_paths(&config, &allowed, Some(&answers), Some(&past_answers)).unwrap();
+        assert_eq!(data.allowed_words, ["crane", "slate", "crate", "trace"]);
+        assert_eq!(data.answers.unwrap(), ["crate", "trace"]);
+        assert_eq!(data.past_answers[0].word, "crate");
+
+        let bad = write("bad.txt", "crane\nsl@te\n");
+        let err = Data::from_paths(&config, &bad, None, None).unwrap_err();
+        assert!(matches!(
+            &err,
+            LoadDataErr::BadEntry { line: 2, reason: EntryErr::NonWordleWord(word), .. }
+                if word == "sl@te"
+        ));
+        assert!(err.to_string().contains("bad.txt line 2"));
+
+        let counts = write("counts.txt", "there 30\nlonger 20\ncrane 10\n");
+        let counts = read_word_counts(&config, &counts).unwrap();
+        assert_eq!(
+            counts,
+            [("there".to_string(), 30.0), ("crane".to_string(), 10.0)]
+        );
+
+        let bad_date = write("bad_date.txt", "2022-02-30 crane\n");
+        assert!(matches!(
+            Data::from_paths(&config, &allowed, None, Some(&bad_date)),
+            Err(LoadDataErr::BadEntry {
+                line: 1,
+                reason: EntryErr::BadDate(_),
+                ..
+            })
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,140 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{color::*, game::*, prelude::*};
+use std::fmt::{Display, Formatter};
+
+///
+/// A single square of a guess which was entered with one coloring, but would need a different
+/// coloring for some word to be the answer.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TileCorrection {
+    /// Which guess the square belongs to (0 = the first guess)
+    pub guess_idx: usize,
+    /// The position of the square within the guess
+    pub idx: usize,
+    /// The letter in the square
+    pub letter: char,
+    /// The coloring that was entered
+    pub entered: Coloring,
+    /// The coloring which the answer would have produced
+    pub corrected: Coloring,
+}
+
+impl Display for TileCorrection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tile {} of guess {} to be {}",
+            self.idx + 1,
+            self.guess_idx + 1,
+            self.corrected.color_name()
+        )
+    }
+}
+
+///
+/// Explains how the entered colorings could be fixed so that a particular word is the answer. This
+/// is used when the colorings contradict each other (no possibilities remain), which usually means
+/// that a square was colored incorrectly by mistake.
+///
+#[derive(Clone, Debug)]
+pub struct Diagnosis<'a> {
+    /// The word which would be the answer after the corrections
+    pub answer: &'a str,
+    /// The weight of the answer (how common the word is)
+    pub weight: WordleFloat,
+    /// The squares which would need to be colored differently, in the order they were guessed
+    pub corrections: Vec<TileCorrection>,
+}
+
+impl<'a> Display for Diagnosis<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, correction) in self.corrections.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " and ")?;
+            }
+            write!(f, "{}", correction)?;
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// Finds every square of the guesses which was colored differently than it would have been if the
+/// answer was the provided word.
+///
+pub fn tile_corrections<'g>(
+    guesses: &'g [&'g Guess],
+    answer: &'g str,
+) -> impl Iterator<Item = TileCorrection> + 'g {
+    guesses
+        .iter()
+        .copied()
+        .enumerate()
+        .flat_map(move |(guess_idx, guess)| {
+            let expected = Colorings::with_guess_answer(guess.word.as_str(), answer);
+            (0..guess.word.len())
+                .filter(move |idx| guess.coloring[*idx] != expected[*idx])
+                .map(move |idx| TileCorrection {
+                    guess_idx,
+                    idx,
+                    letter: guess.word[idx],
+                    entered: guess.coloring[idx],
+                    corrected: expected[idx],
+                })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordle::Coloring::*;
+
+    #[test]
+    fn test_tile_corrections() {
+        let guess = Guess {
+            word: GuessWord::new("chomp"),
+            coloring: [Misplaced, Excluded, Excluded, Excluded, Excluded].into(),
+            expected_info: 0.0,
+            entropy_delta: 0.0,
+        };
+
+        let corrections: Vec<_> = tile_corrections(&[&guess], "watch").collect();
+        assert_eq!(
+            corrections,
+            vec![TileCorrection {
+                guess_idx: 0,
+                idx: 1,
+                letter: 'h',
+                entered: Excluded,
+                corrected: Misplaced,
+            }]
+        );
+        assert_eq!(corrections[0].to_string(), "tile 2 of guess 1 to be yellow");
+        assert_eq!(tile_corrections(&[&guess], "chomp").count(), 5);
+    }
+}
@@ -0,0 +1,234 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{color::*, prelude::*};
+use std::collections::HashMap;
+
+///
+/// The largest number of possible answers that the EndgameSearch can handle. Each subset of the
+/// possible answers is represented as a bitmask in a u64, so we cannot go past 64.
+///
+pub const MAX_ENDGAME_SIZE: usize = u64::BITS as usize;
+
+///
+/// Once this many (or fewer) possibilities remain, the Solver switches from the heuristic scoring
+/// to the exact EndgameSearch by default.
+///
+pub const DEFAULT_ENDGAME_THRESHOLD: usize = 50;
+
+///
+/// An exact search which finds the expected number of guesses needed to solve the puzzle, when
+/// only a small number of possible answers remain.
+///
+/// The expected info heuristic used by the Solver is good at narrowing down a large set of
+/// possibilities, but once only a handful of words remain, what we actually care about is the
+/// number of guesses it takes to find the answer. This search computes that directly:
+///
+///   E(S) = min over guesses g of [ 1 + sum over colorings c != 🟩🟩🟩🟩🟩 of P(c) * E(S_c) ]
+///
+/// Where S is the set of possible answers, S_c is the subset of S which produces coloring c when
+/// guessing g, and P(c) is the probability of S_c (based on the word probabilities). A set with
+/// only a single word takes exactly 1 guess.
+///
+/// Inside of the recursion we only consider guesses from the set of possible answers (S). Every
+/// such guess eliminates at least itself, so the recursion always terminates. The top level
+/// (expected_guesses_for) accepts any word, so probe words can still be evaluated.
+///
+/// Subsets of the possible answers are represented as u64 bitmasks, which allows us to memoize
+/// E(S) very cheaply.
+///
+pub struct EndgameSearch<'a> {
+    /// The possible answers, a subset is represented as a bitmask of indexes into this Vec
+    words: Vec<&'a str>,
+    /// The probability of each word (parallel to words), sums to 1.0
+    probabilities: Vec<WordleFloat>,
+    /// colorings[guess_idx][answer_idx] is the code for the coloring of guess with answer
+    colorings: Vec<Vec<ColoringCode>>,
+    /// The code of the all green coloring, which ends the game
+    solved_code: ColoringCode,
+    /// Memoized output of solve(set)
+    memo: HashMap<u64, WordleFloat>,
+}
+
+impl<'a> EndgameSearch<'a> {
+    ///
+    /// Creates a search over some (word, probability) pairs. There must be no more than
+    /// MAX_ENDGAME_SIZE words.
+    ///
+    pub fn new(answers: impl Iterator<Item = (&'a str, WordleFloat)>) -> Self {
+        let (words, probabilities): (Vec<&'a str>, Vec<WordleFloat>) = answers.unzip();
+        assert!(
+            words.len() <= MAX_ENDGAME_SIZE,
+            "endgame search supports at most {} words, got {}",
+            MAX_ENDGAME_SIZE,
+            words.len()
+        );
+
+        let colorings = words
+            .iter()
+            .map(|guess| {
+                words
+                    .iter()
+                    .map(|answer| Colorings::with_guess_answer(guess, answer).to_code())
+                    .collect()
+            })
+            .collect();
+
+        let word_size = words.first().map_or(WORD_SIZE, |w| num_letters(w));
+        Self {
+            words,
+            probabilities,
+            colorings,
+            solved_code: Colorings::all_correct(word_size).to_code(),
+            memo: HashMap::new(),
+        }
+    }
+
+    ///
+    /// Computes the expected number of guesses to solve the puzzle (including this one) if we
+    /// guess the provided word now, and play perfectly afterwards.
+    ///
+    pub fn expected_guesses_for(&mut self, guess: &str) -> WordleFloat {
+        let all = self.all_words();
+        if all == 0 {
+            return 0.0;
+        }
+
+        let codes: Vec<ColoringCode> = match self.words.iter().position(|w| *w == guess) {
+            Some(guess_idx) => self.colorings[guess_idx].clone(),
+            None => self
+                .words
+                .iter()
+                .map(|answer| Colorings::with_guess_answer(guess, answer).to_code())
+                .collect(),
+        };
+
+        self.expected_guesses_with_codes(all, &codes)
+    }
+
+    ///
+    /// Computes E(S) for the set of words represented by the bitmask
+    ///
+    fn solve(&mut self, set: u64) -> WordleFloat {
+        // a single word can be guessed immediately
+        if set.count_ones() == 1 {
+            return 1.0;
+        }
+
+        if let Some(out) = self.memo.get(&set) {
+            return *out;
+        }
+
+        let mut best = WordleFloat::INFINITY;
+        for guess_idx in iter_set(set) {
+            // this clone is cheap (at most 64 bytes), and avoids borrowing self during recursion
+            let codes = self.colorings[guess_idx].clone();
+            let expected = self.expected_guesses_with_codes(set, &codes);
+            if expected < best {
+                best = expected;
+            }
+        }
+
+        self.memo.insert(set, best);
+        best
+    }
+
+    ///
+    /// Computes 1 + sum of P(c) * E(S_c) for the coloring buckets of the set, where codes holds the
+    /// coloring code for each word (indexed the same as self.words)
+    ///
+    fn expected_guesses_with_codes(&mut self, set: u64, codes: &[ColoringCode]) -> WordleFloat {
+        // split the set into one bitmask per coloring. There can't be more colorings than words, so
+        // the buckets are kept in a small array (searched linearly) instead of one slot per code
+        let mut buckets = [(0 as ColoringCode, 0u64); MAX_ENDGAME_SIZE];
+        let mut num_buckets = 0;
+        for idx in iter_set(set) {
+            let code = codes[idx];
+            match buckets[..num_buckets].iter_mut().find(|(c, _)| *c == code) {
+                Some((_, bucket)) => *bucket |= 1 << idx,
+                None => {
+                    buckets[num_buckets] = (code, 1 << idx);
+                    num_buckets += 1;
+                }
+            }
+        }
+
+        let total_probability = self.probability_of(set);
+        let mut out = 1.0;
+        for (code, bucket) in buckets[..num_buckets].iter().copied() {
+            // when the coloring is all green we're done, so no further guesses are needed
+            if code == self.solved_code {
+                continue;
+            }
+
+            out += (self.probability_of(bucket) / total_probability) * self.solve(bucket);
+        }
+
+        out
+    }
+
+    fn probability_of(&self, set: u64) -> WordleFloat {
+        iter_set(set).map(|idx| self.probabilities[idx]).sum()
+    }
+
+    fn all_words(&self) -> u64 {
+        if self.words.len() == MAX_ENDGAME_SIZE {
+            u64::MAX
+        } else {
+            (1 << self.words.len()) - 1
+        }
+    }
+}
+
+/// Iterates through the indexes of all the set bits in a bitmask
+fn iter_set(mut set: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if set == 0 {
+            None
+        } else {
+            let idx = set.trailing_zeros() as usize;
+            set &= set - 1;
+            Some(idx)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endgame_expected_guesses() {
+        let words = ["batch", "catch", "hatch", "match", "patch", "watch"];
+        let mut search = EndgameSearch::new(words.iter().map(|w| (*w, 1.0 / 6.0)));
+
+        // every guess from the set leaves the other 5 words indistinguishable, so we must go
+        // through them one at a time: (1 + 2 + 3 + 4 + 5 + 6) / 6
+        assert!((search.expected_guesses_for("batch") - 3.5).abs() < 0.000001);
+
+        // chomp splits the words into {batch, hatch, watch}, {catch}, {match}, {patch}, so it is
+        // 1 + 0.5 * E({batch, hatch, watch}) + 0.5 * 1 = 1 + 0.5 * 2 + 0.5
+        assert!((search.expected_guesses_for("chomp") - 2.5).abs() < 0.000001);
+    }
+}
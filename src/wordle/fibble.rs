@@ -0,0 +1,366 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{color::*, diagnosis::*, game::*, prelude::*, scoring::*};
+use std::fmt::{Display, Formatter};
+
+///
+/// How many tiles of each row the host lies about. In Fibble, exactly one tile of every row is
+/// shown with the wrong coloring, so a solver which believes every tile eliminates the answer.
+///
+/// A row which is colored entirely Correct ends the game, so it is never a lie (and no other row
+/// can be shown that way).
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum LieModel {
+    /// Every tile is colored truthfully (regular Wordle)
+    #[default]
+    Honest,
+    /// Exactly this many tiles of each row are colored wrong (Fibble is Exactly(1))
+    Exactly(usize),
+    /// Up to this many tiles of each row may be colored wrong
+    AtMost(usize),
+}
+
+impl LieModel {
+    ///
+    /// Whether or not every coloring can be believed
+    ///
+    pub fn is_honest(&self) -> bool {
+        self.max_lies() == 0
+    }
+
+    ///
+    /// The most tiles which can be lies in a single row
+    ///
+    pub fn max_lies(&self) -> usize {
+        match *self {
+            LieModel::Honest => 0,
+            LieModel::Exactly(n) | LieModel::AtMost(n) => n,
+        }
+    }
+
+    ///
+    /// Whether or not a row can have this many tiles which are lies
+    ///
+    pub fn allows_num_lies(&self, num_lies: usize) -> bool {
+        match *self {
+            LieModel::Honest => num_lies == 0,
+            LieModel::Exactly(n) => num_lies == n,
+            LieModel::AtMost(n) => num_lies <= n,
+        }
+    }
+
+    ///
+    /// Whether or not the host could show the coloring "shown" when the guess really gets the
+    /// coloring "truth"
+    ///
+    pub fn allows_coloring(&self, shown: &Colorings, truth: &Colorings) -> bool {
+        // guessing the answer wins the game, which is never a lie
+        if shown.is_correct() || truth.is_correct() {
+            return shown == truth;
+        }
+
+        self.allows_num_lies(num_lies(shown, truth))
+    }
+
+    ///
+    /// Whether or not the answer could be the word, given the coloring shown for the guess. This is
+    /// Guess::allows_other_guess when the host is honest.
+    ///
+    pub fn allows_answer(&self, guess: &Guess, answer: &str) -> bool {
+        if self.is_honest() {
+            return guess.allows_other_guess(answer);
+        }
+
+        let truth = Colorings::with_guess_answer(guess.word.as_str(), answer);
+        self.allows_coloring(&guess.coloring, &truth)
+    }
+}
+
+impl Display for LieModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let plural = |n: usize| if n == 1 { "lie" } else { "lies" };
+        match *self {
+            LieModel::Honest => write!(f, "no lies"),
+            LieModel::Exactly(n) => write!(f, "exactly {} {} per row", n, plural(n)),
+            LieModel::AtMost(n) => write!(f, "at most {} {} per row", n, plural(n)),
+        }
+    }
+}
+
+///
+/// The number of tiles which are colored differently in the two colorings
+///
+pub fn num_lies(shown: &Colorings, truth: &Colorings) -> usize {
+    shown
+        .as_slice()
+        .iter()
+        .zip(truth.as_slice())
+        .filter(|(a, b)| a != b)
+        .count()
+}
+
+///
+/// Lists every coloring the host could show for each true coloring (by code), so that the coloring
+/// buckets of a guess can be turned into the buckets of what the host will show.
+///
+/// The host is assumed to pick which tiles to lie about (and the coloring to show instead) at
+/// random, so each of the colorings it could show is equally likely.
+///
+#[derive(Clone, Debug)]
+pub struct LieTable {
+    model: LieModel,
+    /// Indexed by the code of the true coloring, empty when the model is honest
+    shown: Vec<Vec<ColoringCode>>,
+}
+
+impl LieTable {
+    pub fn new(model: LieModel, word_size: usize) -> Self {
+        let shown = if model.is_honest() {
+            Vec::new()
+        } else {
+            let all: Vec<Colorings> = (0..Colorings::num_states(word_size))
+                .map(|code| {
+                    Colorings::from_code(code as ColoringCode, word_size).expect("code is in range")
+                })
+                .collect();
+
+            all.iter()
+                .map(|truth| {
+                    all.iter()
+                        .filter(|shown| model.allows_coloring(shown, truth))
+                        .map(Colorings::to_code)
+                        .collect()
+                })
+                .collect()
+        };
+
+        Self { model, shown }
+    }
+
+    pub fn model(&self) -> LieModel {
+        self.model
+    }
+
+    ///
+    /// Turns buckets of the true colorings into buckets of the colorings the host will show. Each
+    /// answer is counted in every bucket that it could be shown as, and its probability is split
+    /// evenly between them.
+    ///
+    /// The randomness of the lies adds entropy to the colorings which tells us nothing about the
+    /// answer, so that is recorded as noise (see ColoringBuckets::expected_info).
+    ///
+    pub fn spread(&self, buckets: &ColoringBuckets) -> ColoringBuckets {
+        if self.model.is_honest() {
+            return buckets.clone();
+        }
+
+        #[allow(clippy::unnecessary_cast)]
+        let mut out = ColoringBuckets {
+            probabilities: vec![0.0 as WordleFloat; buckets.probabilities.len()],
+            sizes: vec![0; buckets.sizes.len()],
+            noise: buckets.noise,
+        };
+
+        for (truth, shown) in self.shown.iter().enumerate() {
+            let (probability, size) = (buckets.probabilities[truth], buckets.sizes[truth]);
+            if size == 0 || shown.is_empty() {
+                continue;
+            }
+
+            let share = probability / shown.len() as WordleFloat;
+            for code in shown {
+                out.probabilities[*code as usize] += share;
+                out.sizes[*code as usize] += size;
+            }
+            out.noise += probability * (shown.len() as WordleFloat).log2();
+        }
+
+        out
+    }
+}
+
+///
+/// A tile which the Solver thinks the host lied about
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LikelyLie {
+    /// The tile, and the coloring it most likely should have had
+    pub tile: TileCorrection,
+    /// The probability that the tile is a lie
+    pub probability: WordleFloat,
+}
+
+impl Display for LikelyLie {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tile {} of guess {} is probably a lie ({:.0}%), it should be {}",
+            self.tile.idx + 1,
+            self.tile.guess_idx + 1,
+            self.probability * 100.0,
+            self.tile.corrected.color_name()
+        )
+    }
+}
+
+///
+/// Finds the tiles of each guess which are most likely to be lies, given the (word, probability) of
+/// every possible answer. At most max_lies tiles are returned for each guess, in the order they
+/// were guessed, and tiles which can't be lies are skipped.
+///
+pub fn likely_lies<'w>(
+    guesses: &[&Guess],
+    answers: impl Iterator<Item = (&'w str, WordleFloat)>,
+    max_lies: usize,
+) -> Vec<LikelyLie> {
+    // the probability of each tile truly having each coloring
+    let mut tallies = vec![[[0.0; Coloring::NUM]; MAX_WORD_SIZE]; guesses.len()];
+    for (answer, probability) in answers {
+        for (guess, tally) in guesses.iter().zip(tallies.iter_mut()) {
+            let truth = Colorings::with_guess_answer(guess.word.as_str(), answer);
+            for (idx, coloring) in truth.as_slice().iter().enumerate() {
+                tally[idx][coloring.ordinal() as usize] += probability;
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    for (guess_idx, (guess, tally)) in guesses.iter().zip(tallies).enumerate() {
+        let mut lies: Vec<LikelyLie> = (0..guess.word.len())
+            .filter_map(|idx| {
+                let entered = guess.coloring[idx];
+                let others = Coloring::ALL
+                    .iter()
+                    .filter(|c| **c != entered)
+                    .map(|c| (*c, tally[idx][c.ordinal() as usize]));
+                let (corrected, _) = others.clone().max_by(|a, b| a.1.total_cmp(&b.1))?;
+                Some(LikelyLie {
+                    tile: TileCorrection {
+                        guess_idx,
+                        idx,
+                        letter: guess.word[idx],
+                        entered,
+                        corrected,
+                    },
+                    probability: others.map(|(_, p)| p).sum(),
+                })
+            })
+            .filter(|lie| lie.probability > 0.0)
+            .collect();
+
+        lies.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+        lies.truncate(max_lies);
+        lies.sort_by_key(|lie| lie.tile.idx);
+        out.extend(lies);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordle::{Coloring::*, GameConfig};
+
+    /// Answers which share the last 4 letters, so a lie about one of those tiles leaves several of
+    /// them possible, plus chomp, which is colored too differently to be explained by one lie
+    fn lie_words() -> Vec<String> {
+        ["latch", "batch", "hatch", "match", "watch", "chomp"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_allows_coloring() {
+        let truth: Colorings = [Excluded, Correct, Correct, Correct, Correct].into();
+        let one_lie: Colorings = [Excluded, Correct, Correct, Correct, Misplaced].into();
+        let two_lies: Colorings = [Misplaced, Correct, Correct, Correct, Misplaced].into();
+
+        let fibble = LieModel::Exactly(1);
+        assert!(fibble.allows_coloring(&one_lie, &truth));
+        assert!(!fibble.allows_coloring(&truth, &truth));
+        assert!(!fibble.allows_coloring(&two_lies, &truth));
+        // winning is never a lie, even though it is only one tile away
+        assert!(!fibble.allows_coloring(&Colorings::all_correct(5), &truth));
+        assert!(fibble.allows_coloring(&Colorings::all_correct(5), &Colorings::all_correct(5)));
+
+        let at_most = LieModel::AtMost(1);
+        assert!(at_most.allows_coloring(&truth, &truth));
+        assert!(at_most.allows_coloring(&one_lie, &truth));
+        assert!(!at_most.allows_coloring(&two_lies, &truth));
+
+        assert_eq!(fibble.to_string(), "exactly 1 lie per row");
+        assert_eq!(LieModel::AtMost(2).to_string(), "at most 2 lies per row");
+    }
+
+    #[test]
+    fn test_spread_adds_no_info() {
+        // a single answer can only be shown one way by an honest host...
+        let buckets = ColoringBuckets::new("latch", [("watch", 1.0)].into_iter());
+        let honest = LieTable::new(LieModel::Honest, 5);
+        assert_eq!(honest.spread(&buckets).sizes, buckets.sizes);
+
+        // but in 9 different ways with one lie (2 choices for each of 5 tiles, except for the one
+        // which would show that the guess won)
+        let spread = LieTable::new(LieModel::Exactly(1), 5).spread(&buckets);
+        assert_eq!(spread.sizes.iter().filter(|size| **size > 0).count(), 9);
+        assert!((spread.probabilities.iter().sum::<WordleFloat>() - 1.0).abs() < 0.0001);
+
+        // which tells us nothing more about the answer
+        assert!(spread.expected_info().abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_lie_model_keeps_answer() {
+        let words = lie_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_lie_model(LieModel::Exactly(1));
+
+        // the answer is watch, so the last tile is a lie (it should be green). Wordle itself could
+        // never show this coloring, because the h would have to be the first letter. Batch, hatch &
+        // match are one lie away too, but chomp would need 3 lies
+        let lie = [Excluded, Correct, Correct, Correct, Misplaced].into();
+        solver.make_guess("latch", lie).unwrap();
+        assert_eq!(solver.num_remaining_possibilities(), 4);
+        assert!(solver.is_possible_answer("watch"));
+        assert!(!solver.is_possible_answer("latch"));
+        assert!(!solver.is_possible_answer("chomp"));
+
+        let lies = solver.likely_lies();
+        assert_eq!(lies.len(), 1);
+        assert_eq!((lies[0].tile.idx, lies[0].tile.corrected), (4, Correct));
+        assert!((lies[0].probability - 1.0).abs() < 0.0001);
+
+        let best = solver.top_k_guesses::<1>().next().unwrap();
+        assert!(best.score.expected_info > 0.0);
+
+        // believing every tile eliminates the answer
+        solver.set_lie_model(LieModel::Honest);
+        assert!(!solver.has_possible_guesses());
+        assert!(solver.likely_lies().is_empty());
+    }
+}
@@ -22,9 +22,16 @@
  * SOFTWARE.
  */
 
-use super::{color::*, data::*, prelude::*};
+use super::{
+    absurdle::*, color::*, coloring_table::*, config::*, constraints::*, data::*, diagnosis::*,
+    endgame::*, fibble::*, guess_model::*, morphology::*, past_answers::*, prelude::*, prior::*,
+    scoring::*, word_bank::*,
+};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Index;
+use std::sync::Arc;
 use thiserror::Error;
 
 ///
@@ -37,6 +44,11 @@ use thiserror::Error;
 ///
 pub type StaticSolver = Solver<'static>;
 
+///
+/// The smallest number of candidates scored by each thread with the "parallel" feature enabled
+///
+const SCORING_CHUNK_SIZE: usize = 64;
+
 ///
 /// Performs the core task of this app- solving wordle!
 ///
@@ -49,12 +61,30 @@ pub type StaticSolver = Solver<'static>;
 /// to constrain users of Solver to only using &'static str, so we make this lifetime argument 'a.
 ///
 pub struct Solver<'a> {
-    /// an unchanging set of all words which you're allowed to guess
-    possible_words: HashSet<&'a str>,
+    /// The rules of the game being solved (word size & number of turns)
+    config: GameConfig,
+
+    /// an unchanging bank of all words which you're allowed to guess, in rank order (most common ->
+    /// least common). Everything else in the solver refers to words by their WordId in this bank.
+    bank: WordBank<'a>,
 
-    /// "weight" of seeing a given word. The values in this map do not sum to 1.0 and aren't
-    /// probabilities, but instead indicate the relative frequency of various possible_words
-    word_weights: HashMap<&'a str, WordleFloat>,
+    /// When set, colorings are looked up in this table instead of being computed each time they
+    /// are needed (see ColoringTable). The table is shared, so it can outlive many games. There is
+    /// no table by default (see set_coloring_table).
+    coloring_table: Option<Arc<ColoringTable>>,
+
+    /// Decides how likely each word is to be the answer, before any guesses are made (see
+    /// WordPrior)
+    word_prior: Box<dyn WordPrior>,
+
+    /// "weight" of seeing a given word according to the word_prior, indexed by WordId. The values
+    /// do not sum to 1.0 and aren't probabilities, but instead indicate the relative frequency of
+    /// the words in the bank
+    prior_weights: Vec<WordleFloat>,
+
+    /// prior_weights, after the repeat_policy has discounted the past answers (and the
+    /// inflection_penalty has discounted the inflections). This is the weight used everywhere else.
+    word_weights: Vec<WordleFloat>,
 
     /// it is extremely expensive to compute the scores in the "default state" (when no guesses have
     /// been made) because the algorithm scales with the square of the possibilities remaining,
@@ -66,26 +96,125 @@ pub struct Solver<'a> {
     /// we generate a text file which contains some top N scores and put that data into this field
     /// at runtime.
     ///
-    /// It is an Option because we need to not load the data from a text file during the generation
-    /// of the text file.
-    default_state_guesses: Option<Vec<ScoredCandidate<'a>>>,
+    /// The cached scores are stored for each of the built-in scoring strategies, keyed by
+    /// ScoringStrategy::id. A cache only applies to the possible answers it was generated for
+    /// (see DefaultStateCache), so there's one for each configuration that data was generated for.
+    /// It is empty when no cached data is available (for example during the generation of the
+    /// text file).
+    default_state_caches: Vec<DefaultStateCache<'a>>,
 
-    /// The guesses that the user has made thus far. It is Option because we start off with None,
-    /// and change to Some when a guess is made.
-    guesses: [Option<Guess>; NUM_TURNS],
+    /// The guesses that the user has made thus far, in the order they were made. There are never
+    /// more than config.max_turns() guesses.
+    guesses: Vec<Guess>,
 
-    /// The subset of possible_words which remain. Possibilities are eliminated as guesses are made,
+    /// The subset of the bank which remain. Possibilities are eliminated as guesses are made,
     /// so this subset is updated upon each guess & gets smaller as the game goes on.
-    remaining_possibilities: HashSet<&'a str>,
+    remaining_possibilities: WordSet,
+
+    /// word_weights, but only the words in "remaining_possibilities" are non-zero, and the values
+    /// are normalized such that they sum to 1.0. Indexed by WordId.
+    word_probabilities: Vec<WordleFloat>,
+
+    /// The entropy of word_probabilities (see remaining_entropy), updated along with it
+    entropy: WordleFloat,
+
+    /// Estimates the number of guesses remaining from the entropy (see expected_guesses_to_solve)
+    guess_count_model: GuessCountModel,
+
+    /// Controls which words are scored when making recommendations (see GuessPool)
+    guess_pool: GuessPool,
+
+    /// The words which the host picks answers from (see Data::answers), if there is such a list
+    answer_list: Option<WordSet>,
+
+    /// Controls which words could be the answer before any guesses are made (see AnswerPool)
+    answer_pool: AnswerPool,
+
+    /// The words which were the answer of past daily puzzles, and the date of that puzzle
+    past_answers: Vec<(WordId, AnswerDate)>,
+
+    /// Controls whether the past_answers can be the answer again (see RepeatPolicy)
+    repeat_policy: RepeatPolicy,
+
+    /// The words which are plurals or past tenses of other words (see Inflection)
+    inflections: Vec<(WordId, Inflection)>,
+
+    /// The weight of the inflections is multiplied by this (1.0 means they aren't penalized)
+    inflection_penalty: WordleFloat,
+
+    /// When true, every guess must reuse all the hints revealed by previous guesses (like the
+    /// "hard mode" setting in Wordle)
+    hard_mode: bool,
+
+    /// When Some(n), the best n candidates (by one-step expected info) are re-scored by looking
+    /// two guesses ahead (see two_step_info)
+    lookahead: Option<usize>,
+
+    /// When Some(n), and n or fewer possibilities remain, candidates are ranked by an exact search
+    /// of the expected number of guesses remaining (see EndgameSearch). Only applies to the scoring
+    /// strategies which want it (see ScoringStrategy::wants_endgame).
+    endgame_threshold: Option<usize>,
+
+    /// Decides how candidates are ranked (see ScoringStrategy)
+    scoring_strategy: Box<dyn ScoringStrategy>,
+
+    /// How the host picks the answer. Against an Adversarial host, candidates are ranked by the
+    /// number of turns needed to force a win (see AdversarySearch)
+    host_model: HostModel,
+
+    /// How many tiles of each row the host may lie about (see LieModel), along with the colorings
+    /// it could show because of those lies
+    lies: LieTable,
+}
+
+///
+/// Controls which words the Solver will score & recommend.
+///
+/// Wordle (outside of hard mode) lets you guess any allowed word, even one that has already been
+/// ruled out as the answer. These "probe" words can never win the game, but they often split the
+/// remaining possibilities far better than any word that could still be the answer.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum GuessPool {
+    /// Only recommend words that could still be the answer (this plays like "hard mode")
+    #[default]
+    RemainingPossibilities,
+    /// Recommend from every allowed word, including words that can no longer be the answer
+    AllowedWords,
+}
+
+///
+/// The cached top guesses (for each strategy) in the default state, which are only valid when the
+/// possible answers (and their weights) are the same as they were when the cache was generated, and
+/// the GuessCountModel (which the ExpectedGuesses strategy uses) hasn't changed
+///
+struct DefaultStateCache<'a> {
+    answers: WordSet,
+    weights: Vec<WordleFloat>,
+    guess_count_model: GuessCountModel,
+    guesses: HashMap<String, Vec<ScoredCandidate<'a>>>,
+}
 
-    /// word_weights, but the keys are the values in "remaining_possibilities" and the values
-    /// are normalized such that they sum to 1.0.
-    word_probabilities: HashMap<&'a str, WordleFloat>,
+///
+/// Controls which words the Solver believes could be the answer.
+///
+/// Wordle picks its answers from a short list of common words, but accepts many more words as
+/// guesses. When that list is known (see Data::answers) only its words need to be considered,
+/// otherwise every allowed word could be the answer and the more common words are more likely.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum AnswerPool {
+    /// Only words in the answer list can be the answer. This behaves like AllowedWords when there
+    /// is no answer list.
+    #[default]
+    AnswerList,
+    /// Every allowed word can be the answer, weighted by how common it is
+    AllowedWords,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Guess {
-    pub word: [u8; WORD_SIZE],
+    pub word: GuessWord,
     pub coloring: Colorings,
     pub expected_info: WordleFloat,
     pub entropy_delta: WordleFloat,
@@ -94,186 +223,269 @@ pub struct Guess {
 impl Guess {
     /// Whether or not the coloring indicates that this guess is the correct answer
     pub fn is_correct(&self) -> bool {
-        self.coloring.0.iter().all(|v| v == &Coloring::Correct)
+        self.coloring.is_correct()
     }
 
     /// Tests if this guess "allows" a different guess. For example: if this guess has a Correct
     /// coloring at position 0 for the letter 'q' but the other uses 'a' in position 0 then that
     /// guess is not allowed.
     pub fn allows_other_guess(&self, other: &str) -> bool {
-        if !is_wordle_str(other) {
-            return false;
-        }
+        num_letters(other) == self.word.len()
+            && is_wordle_str(other)
+            && Colorings::with_guess_answer(self.word.as_str(), other) == self.coloring
+    }
 
-        let is_same = self.is_guess_same(other);
-        let is_self_correct = self.is_correct();
-        // if the 'other' is same as self.guess, we only return true if it's the correct answer
-        // if self is the correct answer, must return early and only return true if we're the correct answer
-        if is_same || is_self_correct {
-            return is_same && is_self_correct;
-        }
+    pub fn is_guess_same(&self, other: &str) -> bool {
+        self.word.as_str() == other
+    }
 
-        // count up how many of each letter we expect to see based on our own coloring
-        let mut unused_letter_counts = [0; ALPHABET_SIZE];
-        for idx in 0..WORD_SIZE {
-            if self.coloring[idx] != Coloring::Excluded {
-                unused_letter_counts[letter_idx(self.word[idx])] += 1;
+    ///
+    /// Checks that the other guess reuses every Correct letter (in the same position) and that it
+    /// contains every letter revealed by this guess, the same way Wordle's hard mode does.
+    ///
+    /// Like Wordle, all the Correct letters are checked before any of the Misplaced letters, so the
+    /// first violation reported is the same one Wordle would show.
+    ///
+    /// The other guess must be a wordle_str.
+    ///
+    pub fn hard_mode_violation(&self, other: &str) -> Option<HardModeViolation> {
+        let other_letters = GuessWord::new(other);
+        for idx in 0..self.word.len() {
+            if self.coloring[idx] == Coloring::Correct && other_letters[idx] != self.word[idx] {
+                return Some(HardModeViolation::MissingCorrect {
+                    idx,
+                    letter: self.word[idx],
+                });
             }
         }
 
-        // determine the letters which we know are excluded
-        let excluded = self.determine_excluded_letters();
-        let other_bytes = other.as_bytes();
+        // a repeated letter which is revealed twice must also be used twice, so we compare counts
+        // instead of just checking that the letter is present
+        let letters = self.word.letters();
+        let revealed_count = |letter: char| {
+            (0..letters.len())
+                .filter(|idx| letters[*idx] == letter && self.coloring[*idx] != Coloring::Excluded)
+                .count()
+        };
+        let other_count = |letter: char| {
+            other_letters
+                .letters()
+                .iter()
+                .filter(|c| **c == letter)
+                .count()
+        };
 
-        // we use idx to index other_bytes, self.word, and self.coloring simultaneously
-        // we know for certain (because this is wordle) that the arrays are all sized according to
-        // WORD_SIZE constant.
-        //
-        // Code generated from this implementation is actually an unrolled loop because the bounds
-        // are based on a constant, but using iterators with zip results in worse performance
-        #[allow(clippy::needless_range_loop)]
-        for idx in 0..WORD_SIZE {
-            let other_c = other_bytes[idx];
-            let self_c = self.word[idx];
-            let coloring = self.coloring[idx];
-            let matches = other_c == self_c;
-
-            // if we have something marked correct, but the "other" uses a different letter, then
-            // obviously this other answer is impossible
-            if coloring == Coloring::Correct && !matches {
-                return false;
-            }
+        letters
+            .iter()
+            .copied()
+            .find(|c| other_count(*c) < revealed_count(*c))
+            .map(|letter| HardModeViolation::MissingMisplaced { letter })
+    }
+}
 
-            // if we have a letter marked misplaced, but the "other" uses the same letter in the
-            // same position, we know the other answer is impossible
-            if coloring == Coloring::Misplaced && matches {
-                return false;
-            }
+///
+/// The letters of a guessed word. They're stored inline (instead of in a String) so that a Guess
+/// can be Copy, which means there's room for the longest supported word (MAX_WORD_SIZE).
+///
+/// Letters outside of ASCII take up to 4 bytes, so the word is stored both as UTF-8 (for as_str)
+/// and as letters (for indexing).
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GuessWord {
+    bytes: [u8; MAX_WORD_BYTES],
+    num_bytes: u8,
+    letters: [char; MAX_WORD_SIZE],
+    len: u8,
+}
 
-            // now check if the letter is excluded explicitly
-            //
-            // We keep a count of the "budget" for all letters either misplaced/correct.
-            //
-            // When we encounter a letter that is not obviously incorrect (based on the above rules)
-            // we will check if we have "budget" for this letter before checking if it's excluded.
-            //
-            // This is because letters that are repeated, where at least one instance is marked as
-            // excluded, will be marked as completely excluded.
-            //
-            // Checking the budget before checking whether it's excluded allows us to correctly
-            // handle repeated letters, such as this case:
-            //    word=abbey, coloring=[CORRECT, CORRECT, EXCLUDED, EXCLUDED, EXCLUDED]
-            // then the letter 'b' would be marked as excluded. If the guess we're testing was
-            // "abhor" then 'b' would be marked as excluded despite being CORRECT. Because we know
-            // the "budget" of "b" is 1, we check that first, decrement it, and then because no
-            // further "b" is in "abhor" the entire function should (correctly) return true
-            let other_letter_idx = letter_idx(other_c);
-            let counter = &mut unused_letter_counts[other_letter_idx];
-            if *counter > 0 {
-                *counter -= 1;
-            } else if excluded[other_letter_idx] {
-                return false;
-            }
+/// The most bytes that the UTF-8 of a GuessWord can take up
+const MAX_WORD_BYTES: usize = MAX_WORD_SIZE * 4;
+
+impl GuessWord {
+    /// The word must be a wordle_str
+    pub fn new(word: &str) -> Self {
+        assert!(is_wordle_str(word), "{:?} is not a wordle word", word);
+        let mut bytes = [0; MAX_WORD_BYTES];
+        bytes[..word.len()].copy_from_slice(word.as_bytes());
+        let mut letters = [' '; MAX_WORD_SIZE];
+        for (slot, letter) in letters.iter_mut().zip(word.chars()) {
+            *slot = letter;
         }
 
-        // now verify that we consumed the entire "budget" of known CORRECT/MISPLACED letters
-        //
-        // this is the last check... if we get to this line all other tests passed, so we can
-        // simply return the result of this final check
-        unused_letter_counts.iter().all(|count| count == &0)
+        Self {
+            bytes,
+            num_bytes: word.len() as u8,
+            letters,
+            len: num_letters(word) as u8,
+        }
     }
 
-    ///
-    /// Outputs true/false flags for each letter in the alphabet, where true indicates that the
-    /// letter has been flagged as "excluded" at least once.
-    ///
-    /// "excluded" might be true for a given letter & it still appears in the word, due to
-    /// repetition of letters.
-    ///
-    fn determine_excluded_letters(&self) -> [bool; ALPHABET_SIZE] {
-        let mut out = [false; ALPHABET_SIZE];
-        for idx in 0..WORD_SIZE {
-            if self.coloring[idx] == Coloring::Excluded {
-                out[letter_idx(self.word[idx])] = true;
-            }
-        }
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.num_bytes as usize])
+            .expect("guesses are always copied from a str")
+    }
 
-        out
+    /// Each letter of the word, in order
+    pub fn letters(&self) -> &[char] {
+        &self.letters[..self.len()]
     }
 
-    pub fn is_guess_same(&self, other: &str) -> bool {
-        if is_wordle_str(other) {
-            let ob = other.as_bytes();
-            self.word.iter().enumerate().all(|(i, c)| ob[i] == *c)
-        } else {
-            false
+    /// The number of letters in the word
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Always false, because a GuessWord always has at least MIN_WORD_SIZE letters
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Index<usize> for GuessWord {
+    type Output = char;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.letters()[index]
+    }
+}
+
+impl Display for GuessWord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Debug for GuessWord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+///
+/// Describes a rule of Wordle's hard mode which was broken by a guess. The Display implementation
+/// produces the same sort of message that Wordle itself shows, like "2nd letter must be R"
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HardModeViolation {
+    /// A letter which was previously marked Correct was not reused at the same position (idx)
+    MissingCorrect { idx: usize, letter: char },
+    /// A letter which was previously revealed was not included in the guess
+    MissingMisplaced { letter: char },
+}
+
+impl Display for HardModeViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HardModeViolation::MissingCorrect { idx, letter } => write!(
+                f,
+                "{}{} letter must be {}",
+                idx + 1,
+                ordinal_suffix(idx + 1),
+                letter.to_ascii_uppercase()
+            ),
+            HardModeViolation::MissingMisplaced { letter } => {
+                write!(f, "guess must contain {}", letter.to_ascii_uppercase())
+            }
         }
     }
 }
 
+/// Gives the suffix used when writing out the number n as an ordinal (like 1st, 2nd, 3rd, 4th)
+pub(crate) fn ordinal_suffix(n: usize) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct ScoredCandidate<'a> {
+    pub id: WordId,
     pub word: &'a str,
     pub score: Score,
+    /// true when this word is still in remaining_possibilities (it could be the answer)
+    pub is_possible_answer: bool,
 }
 
 impl PartialEq<Self> for Score {
     fn eq(&self, other: &Self) -> bool {
-        self.abs.eq(&other.abs)
+        self.abs.eq(&other.abs) && self.tie_break.eq(&other.tie_break)
     }
 }
 
 impl PartialOrd<Self> for Score {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.abs.partial_cmp(&other.abs)
+        match self.abs.partial_cmp(&other.abs) {
+            Some(Ordering::Equal) => self.tie_break.partial_cmp(&other.tie_break),
+            other => other,
+        }
     }
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct Score {
     pub abs: WordleFloat,
+    /// Only compared when abs is equal (see ScoringStrategy::tie_break)
+    pub tie_break: WordleFloat,
     pub expected_info: WordleFloat,
     pub weight: WordleFloat,
+    /// The number of possible answers in the largest coloring bucket (the most possibilities that
+    /// could remain after making this guess)
+    pub worst_case: usize,
+    /// The expected info gained over two turns (this guess + the best follow-up guess). Only
+    /// computed when the Solver has lookahead enabled.
+    pub two_step_info: Option<WordleFloat>,
+    /// The expected number of guesses to solve the puzzle (including this one). Only computed
+    /// during the endgame, see EndgameSearch.
+    pub expected_guesses: Option<WordleFloat>,
 }
 
 impl Score {
-    pub fn new(expected_info: WordleFloat, weight: WordleFloat) -> Self {
-        let abs = Self::calculate_abs(expected_info, weight);
+    pub fn new(strategy: &dyn ScoringStrategy, analysis: &GuessAnalysis) -> Self {
         Self {
-            abs,
-            expected_info,
-            weight,
+            abs: strategy.score(analysis),
+            tie_break: strategy.tie_break(analysis),
+            expected_info: analysis.expected_info,
+            weight: analysis.weight,
+            worst_case: analysis.largest_bucket_size,
+            two_step_info: None,
+            expected_guesses: None,
         }
     }
 
-    pub fn calculate_abs(expected_info: WordleFloat, weight: WordleFloat) -> WordleFloat {
-        expected_info + weight
+    ///
+    /// Attaches the two step expected info to this score, along with the new abs & tie_break
+    /// (which should be computed using the two step info in place of the one step info).
+    ///
+    pub fn with_two_step_info(self, two_step_info: WordleFloat, rescored: Score) -> Self {
+        Self {
+            abs: rescored.abs,
+            tie_break: rescored.tie_break,
+            two_step_info: Some(two_step_info),
+            ..self
+        }
+    }
+
+    ///
+    /// Attaches the expected number of guesses to this score. Fewer guesses is better, so this
+    /// replaces the abs score with the negated number of guesses.
+    ///
+    pub fn with_expected_guesses(self, expected_guesses: WordleFloat) -> Self {
+        Self {
+            abs: -expected_guesses,
+            expected_guesses: Some(expected_guesses),
+            ..self
+        }
     }
 }
 
 /// Implementation of Default uses the embedded data to construct a solver
 impl Default for Solver<'static> {
     fn default() -> Self {
-        let possible_words = DATA.allowed_words.iter().map(|v| v.as_str()).collect();
-
-        let word_weights = compute_word_weights(&DATA.allowed_words).collect();
-        let word_probabilities =
-            compute_word_probabilities(&possible_words, &word_weights).collect();
-        let remaining_possibilities = possible_words.clone();
-        let default_state_guesses = DATA
-            .default_state_data
-            .as_ref()
-            .map(|dsd| compute_default_state_guesses(&possible_words, dsd).collect());
-
-        Self {
-            possible_words,
-            word_weights,
-            default_state_guesses,
-
-            guesses: [None; NUM_TURNS],
-            remaining_possibilities,
-            word_probabilities,
-        }
+        Self::with_config(GameConfig::default(), &DATA)
     }
 }
 
@@ -287,9 +499,215 @@ pub enum SolverErr<'a> {
     AlreadySolved,
     #[error("provided guess is not valid")]
     InvalidGuess(&'a str),
+    #[error("{0}")]
+    HardModeViolation(HardModeViolation),
+    #[error("no guess has been made at index {0}")]
+    NoSuchGuess(usize),
+    #[error("wordle can never color the guess {0}")]
+    ImpossibleColoring(Colorings),
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum WordListErr {
+    #[error("there are no words to guess")]
+    NoWords,
+    #[error("there can be at most {} words, got {0}", WordBank::MAX_WORDS)]
+    TooManyWords(usize),
+    #[error("the word '{0}' is not a valid word for this game")]
+    NonWordleWord(String),
+    #[error("the word '{0}' is listed more than once")]
+    DuplicateWord(String),
+    #[error("the answer '{0}' is not one of the guesses")]
+    UnknownAnswer(String),
+    #[error("expected a weight for each of the {expected} guesses, got {got}")]
+    WrongNumberOfWeights { expected: usize, got: usize },
+    #[error("the weight of '{0}' must be positive, got {1}")]
+    BadWeight(String, WordleFloat),
 }
 
 impl<'a> Solver<'a> {
+    ///
+    /// Constructs a solver for the game described by config, using the words (and cached data) in
+    /// data. The data must have been read for the same word size and alphabet (see
+    /// Data::read_with_config).
+    ///
+    pub fn with_config(config: GameConfig, data: &'a Data) -> Self {
+        assert_eq!(
+            data.word_size,
+            config.word_size(),
+            "data must be read for the config's word size"
+        );
+        assert_eq!(
+            data.alphabet,
+            config.alphabet(),
+            "data must be read for the config's alphabet"
+        );
+        let mut out = Self::with_ordered_words(config, &data.allowed_words, None);
+        out.guess_count_model = data.guess_count_model.unwrap_or_default();
+        if let Some(word_prior) = data.word_prior {
+            out.set_word_prior(Box::new(word_prior));
+        }
+        if let Some(answers) = &data.answers {
+            out.set_answer_list(answers);
+        }
+        out.set_past_answers(&data.past_answers);
+        out.set_inflections(&data.inflections);
+
+        // each cache remembers which words were possible answers (and their weights), so they're
+        // loaded after the prior, answer list, past answers & inflections (using the same
+        // configuration which generated them)
+        if let Some(dsd) = &data.default_state_data {
+            out.add_default_state_cache(dsd);
+        }
+        if let (Some(dsd), Some(cutoff)) = (
+            &data.no_repeats_default_state_data,
+            out.latest_past_answer_date(),
+        ) {
+            out.set_repeat_policy(RepeatPolicy::Exclude { cutoff });
+            out.add_default_state_cache(dsd);
+            out.set_repeat_policy(RepeatPolicy::default());
+        }
+        out
+    }
+
+    ///
+    /// Constructs a solver which can guess any of the ordered_words. The words should be sorted from
+    /// most common -> least common, because the weights are computed from the rank of each word.
+    ///
+    pub(crate) fn with_ordered_words(
+        config: GameConfig,
+        ordered_words: &'a [String],
+        default_state_data: Option<&[DefaultStateEntry]>,
+    ) -> Self {
+        let bank = WordBank::new(ordered_words);
+
+        let word_prior: Box<dyn WordPrior> = Box::<RankSigmoidPrior>::default();
+        let word_weights = word_prior.weights(bank.words());
+        let remaining_possibilities = WordSet::full(bank.len());
+        let word_probabilities =
+            compute_word_probabilities(&remaining_possibilities, &word_weights);
+        let entropy = compute_entropy(&word_probabilities);
+
+        let mut out = Self {
+            config,
+            bank,
+            coloring_table: None,
+            word_prior,
+            prior_weights: word_weights.clone(),
+            word_weights,
+            default_state_caches: Vec::new(),
+
+            guesses: Vec::new(),
+            remaining_possibilities,
+            word_probabilities,
+            entropy,
+            guess_count_model: GuessCountModel::default(),
+            guess_pool: GuessPool::default(),
+            answer_list: None,
+            answer_pool: AnswerPool::default(),
+            past_answers: Vec::new(),
+            repeat_policy: RepeatPolicy::default(),
+            inflections: Vec::new(),
+            inflection_penalty: DEFAULT_INFLECTION_PENALTY,
+            hard_mode: false,
+            lookahead: None,
+            endgame_threshold: Some(DEFAULT_ENDGAME_THRESHOLD),
+            scoring_strategy: Box::new(EntropyPlusWeight),
+            host_model: HostModel::default(),
+            lies: LieTable::new(LieModel::default(), config.word_size()),
+        };
+        if let Some(dsd) = default_state_data {
+            out.add_default_state_cache(dsd);
+        }
+        out
+    }
+
+    ///
+    /// Constructs a solver for the game described by config from word lists, instead of from the
+    /// embedded data (see Data::from_paths to read them from files):
+    ///
+    /// * guesses are all the words which can be guessed, from most common to least common
+    /// * answers (optional) are the words which can be the answer, they must also be guesses. When
+    ///   there's no answer list, every guess can be the answer (see AnswerPool).
+    /// * weights (optional) are how likely each of the guesses is to be the answer (relative to
+    ///   each other), parallel to guesses (see WordCountPrior). Without them, the weights come from
+    ///   the rank of each word (see RankSigmoidPrior).
+    ///
+    pub fn from_words(
+        config: GameConfig,
+        answers: Option<&[String]>,
+        guesses: &'a [String],
+        weights: Option<&[WordleFloat]>,
+    ) -> Result<Self, WordListErr> {
+        if guesses.is_empty() {
+            return Err(WordListErr::NoWords);
+        }
+        if guesses.len() > WordBank::MAX_WORDS {
+            return Err(WordListErr::TooManyWords(guesses.len()));
+        }
+
+        let mut seen = HashSet::new();
+        for guess in guesses {
+            if !config.is_word(guess) {
+                return Err(WordListErr::NonWordleWord(guess.clone()));
+            }
+            if !seen.insert(guess.as_str()) {
+                return Err(WordListErr::DuplicateWord(guess.clone()));
+            }
+        }
+
+        if let Some(answer) = answers
+            .unwrap_or_default()
+            .iter()
+            .find(|answer| !seen.contains(answer.as_str()))
+        {
+            return Err(WordListErr::UnknownAnswer(answer.clone()));
+        }
+
+        if let Some(weights) = weights {
+            if weights.len() != guesses.len() {
+                return Err(WordListErr::WrongNumberOfWeights {
+                    expected: guesses.len(),
+                    got: weights.len(),
+                });
+            }
+            if let Some((guess, weight)) = guesses
+                .iter()
+                .zip(weights)
+                .find(|(_, weight)| !(weight.is_finite() && **weight > 0.0))
+            {
+                return Err(WordListErr::BadWeight(guess.clone(), *weight));
+            }
+        }
+
+        let mut out = Self::with_ordered_words(config, guesses, None);
+        if let Some(weights) = weights {
+            out.set_word_prior(Box::new(WordCountPrior::new(
+                guesses.iter().cloned().zip(weights.iter().copied()),
+            )));
+        }
+        match answers {
+            Some(answers) => out.set_answer_list(answers),
+            None => out.recompute_from_guesses(),
+        }
+        Ok(out)
+    }
+
+    ///
+    /// Caches the default state data, which must have been generated for the current possible
+    /// answers (see DefaultStateCache)
+    ///
+    fn add_default_state_cache(&mut self, default_state_data: &[DefaultStateEntry]) {
+        let answers = self.possible_answers();
+        let guesses = compute_default_state_guesses(&self.bank, &answers, default_state_data);
+        self.default_state_caches.push(DefaultStateCache {
+            answers,
+            weights: self.word_weights.clone(),
+            guess_count_model: self.guess_count_model,
+            guesses,
+        });
+    }
+
     ///
     /// Add a guess to the solver so that it makes new recommendations based on the new information
     /// provided by the user.
@@ -308,6 +726,175 @@ impl<'a> Solver<'a> {
         'g: 's,
     {
         let guess = guess.trim();
+        self.check_can_make_guess()?;
+
+        // ensure the provided guess is a word of the right size in lowercase ascii (which we know
+        // about)
+        let guess_id = match self.bank.id(guess) {
+            Some(id) if self.config.is_word(guess) => id,
+            _ => return Err(SolverErr::InvalidGuess(guess)),
+        };
+
+        // some colorings can't ever be shown by wordle, and they're almost always a typo. A lie can
+        // produce one of those colorings though, so they're only rejected when nobody is lying
+        if self.lie_model().is_honest() && !coloring.is_consistent_with_guess(guess) {
+            return Err(SolverErr::ImpossibleColoring(coloring));
+        }
+
+        self.check_hard_mode(guess)?;
+        self.push_guess(guess_id, coloring);
+        Ok(())
+    }
+
+    ///
+    /// Removes the most recent guess, putting the solver back into the state it was in before that
+    /// guess was made. Returns the guess which was removed, or None if no guesses have been made.
+    ///
+    pub fn undo_last_guess(&mut self) -> Option<Guess> {
+        let out = self.guesses.pop();
+        self.recompute_from_guesses();
+        out
+    }
+
+    ///
+    /// Changes the word and/or coloring of a guess that was already made (idx is the turn, starting
+    /// at 0). Every guess made after it is re-applied, so their entropy_delta & expected_info are
+    /// updated to reflect the new information.
+    ///
+    /// If the edited guess (or any of the guesses after it) can't be made anymore, for example
+    /// because it makes a later guess break the hard mode rules, then the solver is left unchanged
+    /// and the error is returned.
+    ///
+    pub fn replace_guess<'g>(
+        &mut self,
+        idx: usize,
+        guess: &'g str,
+        coloring: Colorings,
+    ) -> Result<(), SolverErr<'g>> {
+        if idx >= self.num_guesses() {
+            return Err(SolverErr::NoSuchGuess(idx));
+        }
+
+        self.remake_guesses_from(idx, |solver, previous_guesses| {
+            solver.make_guess(guess, coloring)?;
+            previous_guesses[idx + 1..]
+                .iter()
+                .try_for_each(|later| solver.replay_guess(later))?;
+            Ok(())
+        })
+    }
+
+    ///
+    /// When the colorings entered so far contradict each other (no possibilities remain), this
+    /// finds the words which could be the answer if a few squares were colored differently. The
+    /// output is ranked by the fewest number of changed squares, then by the weight of the word, so
+    /// the first item is the most likely fix for the mistake.
+    ///
+    /// Words which were guessed before the final guess are never suggested, because the game would
+    /// have ended when they were guessed.
+    ///
+    pub fn diagnose_contradiction<const K: usize>(&self) -> Vec<Diagnosis<'a>> {
+        let guesses: Vec<&Guess> = self.iter_guesses().collect();
+        let earlier_guesses = &guesses[..guesses.len().saturating_sub(1)];
+
+        // count first, and only collect the corrections for the few words which are kept
+        self.bank
+            .ids()
+            .filter(|id| {
+                let word = self.bank.word(*id);
+                !earlier_guesses.iter().any(|g| g.is_guess_same(word))
+            })
+            .map(|id| {
+                let word = self.bank.word(id);
+                (id, tile_corrections(&guesses, word).count())
+            })
+            .top_k::<_, _, K>(|(id, n_corrections)| {
+                (
+                    std::cmp::Reverse(*n_corrections),
+                    self.word_weights[*id as usize],
+                )
+            })
+            .map(|(id, _)| {
+                let answer = self.bank.word(id);
+                Diagnosis {
+                    answer,
+                    weight: self.word_weights[id as usize],
+                    corrections: tile_corrections(&guesses, answer).collect(),
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Recolors the squares described by the diagnosis (see diagnose_contradiction), re-applying
+    /// every guess after the first one which changed. The solver is unchanged if this fails.
+    ///
+    pub fn apply_diagnosis(&mut self, diagnosis: &Diagnosis) -> Result<(), SolverErr<'static>> {
+        let first_idx = match diagnosis.corrections.iter().map(|c| c.guess_idx).min() {
+            Some(idx) => idx,
+            None => return Ok(()),
+        };
+
+        self.remake_guesses_from(first_idx, |solver, previous_guesses| {
+            for (guess_idx, guess) in previous_guesses.iter().enumerate().skip(first_idx) {
+                let mut guess = *guess;
+                for correction in &diagnosis.corrections {
+                    if correction.guess_idx == guess_idx {
+                        guess.coloring[correction.idx] = correction.corrected;
+                    }
+                }
+
+                solver.replay_guess(&guess)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    ///
+    /// Removes all the guesses starting at idx, then lets make_guesses make them again (it is given
+    /// a copy of the guesses from before they were removed). If make_guesses fails, every guess is
+    /// put back the way it was.
+    ///
+    fn remake_guesses_from<'g>(
+        &mut self,
+        idx: usize,
+        make_guesses: impl FnOnce(&mut Self, &[Guess]) -> Result<(), SolverErr<'g>>,
+    ) -> Result<(), SolverErr<'g>> {
+        let previous_guesses = self.guesses.clone();
+        self.guesses.truncate(idx);
+        self.recompute_from_guesses();
+
+        let result = make_guesses(self, &previous_guesses);
+        if result.is_err() {
+            self.guesses = previous_guesses;
+            self.recompute_from_guesses();
+        }
+
+        result
+    }
+
+    ///
+    /// Makes a guess again which was previously made (see replace_guess), checking that it is still
+    /// allowed given the guesses before it.
+    ///
+    fn replay_guess(&mut self, guess: &Guess) -> Result<(), SolverErr<'static>> {
+        self.check_can_make_guess()?;
+        let word = guess.word.as_str();
+        let guess_id = self
+            .bank
+            .id(word)
+            .expect("previous guesses must be in the word bank");
+
+        self.check_hard_mode(word)?;
+        self.push_guess(guess_id, guess.coloring);
+        Ok(())
+    }
+
+    ///
+    /// Checks that the state of the game allows another guess
+    ///
+    fn check_can_make_guess(&self) -> Result<(), SolverErr<'static>> {
         // if we're solved, we cannot make a guess
         if self.is_solved() {
             return Err(SolverErr::AlreadySolved);
@@ -318,28 +905,38 @@ impl<'a> Solver<'a> {
             return Err(SolverErr::NoCandidates);
         }
 
-        // try to find out which index to put this guess into, and if we can't find one it's because
-        // we've already made the maximum number of guesses (so we return an error with ?)
-        let next_guess_idx = self.next_guess_idx().ok_or(SolverErr::TurnsExhausted)?;
+        // we may have already made the maximum number of guesses
+        if !self.has_turns_remaining() {
+            return Err(SolverErr::TurnsExhausted);
+        }
+
+        Ok(())
+    }
 
-        // ensure the provided guess is a 5 letter word in lowercase ascii
-        if !is_wordle_str(&guess) || !self.is_guess_permitted(&guess) {
-            return Err(SolverErr::InvalidGuess(guess));
+    ///
+    /// In hard mode, the guess must also reuse all the hints we've been given so far
+    ///
+    fn check_hard_mode(&self, guess: &str) -> Result<(), SolverErr<'static>> {
+        match self.hard_mode_violation(guess) {
+            Some(violation) => Err(SolverErr::HardModeViolation(violation)),
+            None => Ok(()),
         }
+    }
 
-        // copy guess characters to a fixed size byte array (we cannot use .as_bytes() because it's
-        // a fixed size array [u8; WORD_SIZE(5)], not a &[u8])
-        let mut word = [0u8; WORD_SIZE];
-        word.copy_from_slice(guess.as_bytes());
+    ///
+    /// Stores the guess after the others & updates the solver to reflect the information it provides
+    ///
+    fn push_guess(&mut self, guess_id: WordId, coloring: Colorings) {
+        let word = GuessWord::new(self.bank.word(guess_id));
 
         // track entropy in the puzzle, so we can calculate the delta after making the guess
         let start_entropy = self.remaining_entropy();
 
-        // store the guess in the guesses array
-        self.guesses[next_guess_idx] = Some(Guess {
+        // store the guess after the other guesses
+        self.guesses.push(Guess {
             coloring,
             word,
-            expected_info: self.expected_guess_info(&guess),
+            expected_info: self.expected_guess_info(guess_id),
             entropy_delta: 0.0,
         });
 
@@ -349,9 +946,7 @@ impl<'a> Solver<'a> {
         // re-calculate the puzzle entropy
         let new_entropy = self.remaining_entropy();
         // update the entropy_delta of the guess we just made, now that we can compute
-        self.guesses[next_guess_idx].as_mut().unwrap().entropy_delta = start_entropy - new_entropy;
-
-        Ok(())
+        self.guesses.last_mut().unwrap().entropy_delta = start_entropy - new_entropy;
     }
 
     fn recompute_after_guess(&mut self) {
@@ -360,38 +955,96 @@ impl<'a> Solver<'a> {
     }
 
     ///
-    /// This updates self.remaining_possibilities such that it only contains possible guesses given
-    /// the "rules" specified by the colorings.
-    ///
-    /// For example, if we have a single guess like "quack" with coloring [🟩,🟩,X,🟩,🟩], then we
-    /// can clearly eliminate a possible answer such as "tares" because "q" must be in the first
-    /// position.
+    /// Recomputes remaining_possibilities (and everything derived from it) from scratch, which is
+    /// needed when guesses are removed instead of added
     ///
-    fn recompute_possibilities(&mut self) {
-        // retain removes items from the set when the closure returns false
-        self.remaining_possibilities
-            .retain(|word| is_guess_allowed_by_existing_guesses(&self.guesses, *word))
+    fn recompute_from_guesses(&mut self) {
+        self.remaining_possibilities = self.possible_answers();
+        self.recompute_after_guess();
     }
 
     ///
-    /// This recalculates the data in self.word_probabilities to meet word_probabilities' definition.
-    /// Should be called whenever remaining_possibilities is updated...
+    /// The words which could be the answer before any guesses are made (see AnswerPool)
     ///
-    fn recompute_word_probabilities(&mut self) {
-        self.word_probabilities.clear();
-        self.word_probabilities.extend(compute_word_probabilities(
-            &self.remaining_possibilities,
-            &self.word_weights,
-        ));
+    fn possible_answers(&self) -> WordSet {
+        let mut out = match (self.answer_pool, &self.answer_list) {
+            (AnswerPool::AnswerList, Some(answers)) => answers.clone(),
+            _ => WordSet::full(self.bank.len()),
+        };
+        for (id, date) in &self.past_answers {
+            if self.repeat_policy.weight_factor(*date).is_none() {
+                out.remove(*id);
+            }
+        }
+        out
+    }
+
+    ///
+    /// Recomputes word_weights from prior_weights, discounting past answers (see RepeatPolicy) and
+    /// inflections (see Inflection)
+    ///
+    fn recompute_word_weights(&mut self) {
+        self.word_weights.clone_from(&self.prior_weights);
+        for (id, date) in &self.past_answers {
+            if let Some(factor) = self.repeat_policy.weight_factor(*date) {
+                self.word_weights[*id as usize] *= factor;
+            }
+        }
+        for (id, _) in &self.inflections {
+            self.word_weights[*id as usize] *= self.inflection_penalty;
+        }
+    }
+
+    ///
+    /// This updates self.remaining_possibilities such that it only contains possible guesses given
+    /// the "rules" specified by the colorings.
+    ///
+    /// For example, if we have a single guess like "quack" with coloring [🟩,🟩,X,🟩,🟩], then we
+    /// can clearly eliminate a possible answer such as "tares" because "q" must be in the first
+    /// position.
+    ///
+    /// When the host may lie, each guess instead allows any answer whose coloring differs from the
+    /// one shown by an allowed number of lies (see LieModel::allows_answer).
+    ///
+    fn recompute_possibilities(&mut self) {
+        // retain removes items from the set when the closure returns false
+        let bank = &self.bank;
+        let lie_model = self.lies.model();
+        if lie_model.is_honest() {
+            let constraints = self.constraints();
+            self.remaining_possibilities
+                .retain(|id| constraints.matches(bank.word(id)))
+        } else {
+            let guesses = &self.guesses;
+            self.remaining_possibilities.retain(|id| {
+                guesses
+                    .iter()
+                    .all(|guess| lie_model.allows_answer(guess, bank.word(id)))
+            })
+        }
+    }
+
+    ///
+    /// Everything that the guesses made so far tell us about the answer (see Constraints). This
+    /// believes every tile, so it is only accurate when the host is honest (see LieModel).
+    ///
+    pub fn constraints(&self) -> Constraints {
+        Constraints::from_guesses(&self.config, self.iter_guesses())
+    }
+
+    ///
+    /// This recalculates the data in self.word_probabilities to meet word_probabilities' definition.
+    /// Should be called whenever remaining_possibilities is updated...
+    ///
+    fn recompute_word_probabilities(&mut self) {
+        self.word_probabilities =
+            compute_word_probabilities(&self.remaining_possibilities, &self.word_weights);
+        self.entropy = compute_entropy(&self.word_probabilities);
 
         debug_assert!(
-            self.word_probabilities.is_empty() || {
-                let sum = self
-                    .word_probabilities
-                    .values()
-                    .copied()
-                    .sum::<WordleFloat>();
-                sum.abs() < 0.000001
+            self.remaining_possibilities.is_empty() || {
+                let sum = self.word_probabilities.iter().copied().sum::<WordleFloat>();
+                (sum - 1.0).abs() < 0.000001
             },
             "weights must add up to exactly 1.0",
         );
@@ -404,15 +1057,31 @@ impl<'a> Solver<'a> {
     ///   * Turns are exhausted
     ///
     pub fn can_guess(&self) -> bool {
-        self.num_guesses() < NUM_TURNS && !self.is_solved() && self.has_possible_guesses()
+        self.has_turns_remaining() && !self.is_solved() && self.has_possible_guesses()
+    }
+
+    ///
+    /// Whether or not the config allows another guess to be made (see GameConfig::max_turns)
+    ///
+    pub fn has_turns_remaining(&self) -> bool {
+        self.config
+            .max_turns()
+            .is_none_or(|max_turns| self.num_guesses() < max_turns)
+    }
+
+    ///
+    /// The rules of the game being solved
+    ///
+    pub fn config(&self) -> &GameConfig {
+        &self.config
     }
 
     ///
-    /// This function simply tests if a guess is in the possible_words set. This does not indicate
-    /// whether a guess is allowed given guesses that have already been made.
+    /// This function simply tests if a guess is in the word bank. This does not indicate whether a
+    /// guess is allowed given guesses that have already been made.
     ///
     pub fn is_guess_permitted(&self, guess: &str) -> bool {
-        self.possible_words.contains(guess)
+        self.bank.contains(guess)
     }
 
     ///
@@ -429,7 +1098,7 @@ impl<'a> Solver<'a> {
     /// Returns the number of guesses already made
     ///
     pub fn num_guesses(&self) -> usize {
-        self.next_guess_idx().unwrap_or(NUM_TURNS)
+        self.guesses.len()
     }
 
     ///
@@ -451,40 +1120,461 @@ impl<'a> Solver<'a> {
     /// Returns the number of possible guesses, without considering any guesses that have been made
     ///
     pub fn num_total_possibilities(&self) -> usize {
-        self.possible_words.len()
+        self.possible_answers().len()
+    }
+
+    ///
+    /// All the words which can be guessed, each with a WordId
+    ///
+    pub fn word_bank(&self) -> &WordBank<'a> {
+        &self.bank
     }
 
     ///
-    /// Determines the "entropy" of the puzzle given the guesses that remain.
+    /// The WordIds of the words which could still be the answer
     ///
-    /// todo document this
+    pub fn remaining_possibilities(&self) -> &WordSet {
+        &self.remaining_possibilities
+    }
+
+    ///
+    /// The probability that the word with the given id is the answer (0 once it's been ruled out)
+    ///
+    pub fn word_probability(&self, id: WordId) -> WordleFloat {
+        self.word_probabilities[id as usize]
+    }
+
+    ///
+    /// The uncertainty (in bits) about which word is the answer, given the guesses made so far.
+    /// This is the entropy of the answer probabilities (see word_probability), so it's
+    /// log2(n) when n equally likely words remain and 0 once the answer is known. A guess is
+    /// expected to remove as many bits as its expected info.
     ///
     pub fn remaining_entropy(&self) -> WordleFloat {
-        self.word_probabilities
-            .values()
-            .map(|v| v * -(v.log2()))
-            .sum()
+        self.entropy
     }
 
     ///
-    /// Returns the first empty index in the self.guesses array. Indicates many things:
-    ///   * How many guesses have been made = the output of this function
-    ///   * None = turns exhausted
+    /// Estimates how many more guesses (including the winning guess) it will take to solve the
+    /// puzzle, using the GuessCountModel. Returns None if no more guesses can be made.
     ///
-    /// This function's primary purpose, however, is to provide the index to store a new guess at
+    pub fn expected_guesses_to_solve(&self) -> Option<WordleFloat> {
+        if !self.can_guess() {
+            return None;
+        }
+
+        Some(if self.num_remaining_possibilities() == 1 {
+            1.0
+        } else {
+            self.guess_count_model.expected_guesses(self.entropy)
+        })
+    }
+
     ///
-    fn next_guess_idx(&self) -> Option<usize> {
-        // find the first None item in self.guesses
-        for (idx, v) in self.guesses.iter().enumerate() {
-            if v.is_none() {
-                // return it's index
-                return Some(idx);
-            }
+    /// The model used by expected_guesses_to_solve and the ExpectedGuesses strategy (by default,
+    /// the one fitted by gen_guess_model)
+    ///
+    pub fn guess_count_model(&self) -> GuessCountModel {
+        self.guess_count_model
+    }
+
+    ///
+    /// Changes how the entropy remaining is turned into a number of guesses (see GuessCountModel)
+    ///
+    pub fn set_guess_count_model(&mut self, guess_count_model: GuessCountModel) {
+        self.guess_count_model = guess_count_model;
+    }
+
+    ///
+    /// Returns which words are considered when making recommendations
+    ///
+    pub fn guess_pool(&self) -> GuessPool {
+        self.guess_pool
+    }
+
+    ///
+    /// Changes which words are considered when making recommendations. This does not affect which
+    /// guesses are permitted, only which words are scored by top_k_guesses.
+    ///
+    pub fn set_guess_pool(&mut self, guess_pool: GuessPool) {
+        self.guess_pool = guess_pool;
+    }
+
+    ///
+    /// Returns which words could be the answer before any guesses are made
+    ///
+    pub fn answer_pool(&self) -> AnswerPool {
+        self.answer_pool
+    }
+
+    ///
+    /// Changes which words could be the answer. Every allowed word can still be guessed, but the
+    /// remaining possibilities are recomputed using the guesses made so far.
+    ///
+    pub fn set_answer_pool(&mut self, answer_pool: AnswerPool) {
+        self.answer_pool = answer_pool;
+        self.recompute_from_guesses();
+    }
+
+    ///
+    /// Whether or not the host's list of answers is known (see AnswerPool::AnswerList)
+    ///
+    pub fn has_answer_list(&self) -> bool {
+        self.answer_list.is_some()
+    }
+
+    ///
+    /// Returns whether or not the past answers can be the answer again
+    ///
+    pub fn repeat_policy(&self) -> RepeatPolicy {
+        self.repeat_policy
+    }
+
+    ///
+    /// Changes whether or not the past answers can be the answer again. The remaining possibilities
+    /// are recomputed using the guesses made so far.
+    ///
+    pub fn set_repeat_policy(&mut self, repeat_policy: RepeatPolicy) {
+        self.repeat_policy = repeat_policy;
+        self.recompute_word_weights();
+        self.recompute_from_guesses();
+    }
+
+    ///
+    /// The date of the most recent past answer, None if there are no past answers. Using this as
+    /// the cutoff of a RepeatPolicy affects every past answer.
+    ///
+    pub fn latest_past_answer_date(&self) -> Option<AnswerDate> {
+        self.past_answers.iter().map(|(_, date)| *date).max()
+    }
+
+    ///
+    /// Remembers the answers of past daily puzzles, which the RepeatPolicy applies to. Past answers
+    /// which aren't allowed words are ignored (they can't be the answer anyway).
+    ///
+    pub(crate) fn set_past_answers(&mut self, past_answers: &[PastAnswer]) {
+        self.past_answers = past_answers
+            .iter()
+            .filter_map(|past| self.bank.id(&past.word).map(|id| (id, past.date)))
+            .collect();
+        self.recompute_word_weights();
+        self.recompute_from_guesses();
+    }
+
+    ///
+    /// Returns how much the weight of the inflected words is multiplied by (see
+    /// set_inflection_penalty)
+    ///
+    pub fn inflection_penalty(&self) -> WordleFloat {
+        self.inflection_penalty
+    }
+
+    ///
+    /// Changes how much less likely the plurals & past tenses are to be the answer. The penalty is
+    /// a factor between 0 (exclusive) and 1, where 1 means they're as likely as any other word.
+    ///
+    pub fn set_inflection_penalty(&mut self, penalty: WordleFloat) {
+        assert!(
+            penalty > 0.0 && penalty <= 1.0,
+            "inflection penalty must be in (0, 1], got {}",
+            penalty
+        );
+        self.inflection_penalty = penalty;
+        self.recompute_word_weights();
+        self.recompute_word_probabilities();
+    }
+
+    ///
+    /// Returns which inflection the word is, or None if it isn't one (or isn't an allowed word)
+    ///
+    pub fn inflection(&self, word: &str) -> Option<Inflection> {
+        let id = self.bank.id(word)?;
+        self.inflections
+            .iter()
+            .find(|(inflected, _)| *inflected == id)
+            .map(|(_, inflection)| *inflection)
+    }
+
+    ///
+    /// Whether or not any of the allowed words are known to be inflections
+    ///
+    pub fn has_inflections(&self) -> bool {
+        !self.inflections.is_empty()
+    }
+
+    ///
+    /// Remembers which words are inflections of other words, which the inflection_penalty applies
+    /// to. Inflections which aren't allowed words are ignored.
+    ///
+    pub(crate) fn set_inflections(&mut self, inflections: &[InflectedWord]) {
+        self.inflections = inflections
+            .iter()
+            .filter_map(|inflected| {
+                self.bank
+                    .id(&inflected.word)
+                    .map(|id| (id, inflected.inflection))
+            })
+            .collect();
+        self.recompute_word_weights();
+        self.recompute_word_probabilities();
+    }
+
+    ///
+    /// Restricts the answers to the given words, which must all be allowed words. Only affects the
+    /// remaining possibilities when using AnswerPool::AnswerList.
+    ///
+    pub(crate) fn set_answer_list(&mut self, answers: &[String]) {
+        let mut answer_list = WordSet::empty(self.bank.len());
+        for answer in answers {
+            let id = self
+                .bank
+                .id(answer)
+                .unwrap_or_else(|| panic!("the answer {} should be an allowed word", answer));
+            answer_list.insert(id);
+        }
+        self.answer_list = Some(answer_list);
+        self.recompute_from_guesses();
+    }
+
+    ///
+    /// Whether or not guesses must follow the hard mode rules (see Guess::hard_mode_violation)
+    ///
+    pub fn is_hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    ///
+    /// Turns hard mode on or off. Guesses which were already made are not re-checked.
+    ///
+    pub fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+
+    ///
+    /// When hard mode is enabled, this returns the first hard mode rule that the guess would break
+    /// given the guesses already made. Always returns None when hard mode is disabled.
+    ///
+    /// The guess must be a wordle_str.
+    ///
+    pub fn hard_mode_violation(&self, guess: &str) -> Option<HardModeViolation> {
+        if !self.hard_mode {
+            return None;
+        }
+
+        self.iter_guesses()
+            .find_map(|g| g.hard_mode_violation(guess))
+    }
+
+    ///
+    /// Returns how many candidates are re-scored by looking two guesses ahead (None = disabled)
+    ///
+    pub fn lookahead(&self) -> Option<usize> {
+        self.lookahead
+    }
+
+    ///
+    /// Enables (Some) or disables (None) the two step lookahead. When enabled with Some(n), the n
+    /// best candidates according to the normal one step score are re-ranked by the total expected
+    /// info over two turns. They come first in top_k_guesses, followed by the other candidates in
+    /// one step order.
+    ///
+    /// This is much more expensive than the one step scoring, especially in the default state or
+    /// when recommending from all allowed words, so keep n small.
+    ///
+    pub fn set_lookahead(&mut self, lookahead: Option<usize>) {
+        self.lookahead = lookahead;
+    }
+
+    ///
+    /// Returns the number of remaining possibilities at which the exact endgame search takes over
+    /// from the heuristic scoring (None = never)
+    ///
+    pub fn endgame_threshold(&self) -> Option<usize> {
+        self.endgame_threshold
+    }
+
+    ///
+    /// Changes when the exact endgame search takes over from the heuristic scoring. The threshold
+    /// is capped at MAX_ENDGAME_SIZE.
+    ///
+    pub fn set_endgame_threshold(&mut self, endgame_threshold: Option<usize>) {
+        self.endgame_threshold = endgame_threshold.map(|n| n.min(MAX_ENDGAME_SIZE));
+    }
+
+    ///
+    /// Whether or not few enough possibilities remain that recommendations come from the exact
+    /// endgame search
+    ///
+    /// The search assumes that every coloring is honest, so it is never used when the host may lie.
+    /// Strategies which rank by something other than the expected number of guesses (see
+    /// ScoringStrategy::wants_endgame) also keep their own ranking.
+    ///
+    pub fn is_endgame(&self) -> bool {
+        self.scoring_strategy.wants_endgame()
+            && self.lie_model().is_honest()
+            && self
+                .endgame_threshold
+                .map(|n| self.num_remaining_possibilities() <= n)
+                .unwrap_or(false)
+    }
+
+    ///
+    /// Returns the prior which decides how likely each word is to be the answer
+    ///
+    pub fn word_prior(&self) -> &dyn WordPrior {
+        self.word_prior.as_ref()
+    }
+
+    ///
+    /// Changes how likely each word is to be the answer (see WordPrior). The probabilities of the
+    /// remaining possibilities are recomputed, but the guesses made so far are not re-scored. The
+    /// cached default state data is only available for the default prior.
+    ///
+    pub fn set_word_prior(&mut self, word_prior: Box<dyn WordPrior>) {
+        self.prior_weights = word_prior.weights(self.bank.words());
+        self.word_prior = word_prior;
+        self.recompute_word_weights();
+        self.recompute_word_probabilities();
+    }
+
+    ///
+    /// Returns the strategy used to rank candidates
+    ///
+    pub fn scoring_strategy(&self) -> &dyn ScoringStrategy {
+        self.scoring_strategy.as_ref()
+    }
+
+    ///
+    /// Changes the strategy used to rank candidates. Note that the cached default state data is only
+    /// available for the built-in strategies, so the first recommendations are slow to compute with
+    /// any other strategy.
+    ///
+    pub fn set_scoring_strategy(&mut self, scoring_strategy: Box<dyn ScoringStrategy>) {
+        self.scoring_strategy = scoring_strategy;
+    }
+
+    ///
+    /// The ColoringTable used for scoring, if any
+    ///
+    pub fn coloring_table(&self) -> Option<&Arc<ColoringTable>> {
+        self.coloring_table.as_ref()
+    }
+
+    ///
+    /// Sets the ColoringTable used for scoring. The table must have been created with the same
+    /// words as this solver, in the same (rank) order.
+    ///
+    /// Without a table (the default) every coloring is computed when it is needed. A table makes
+    /// scoring much faster when it's reused, but it costs one byte for every pair of words: a full
+    /// table for the ~15k default words is ~220MB, which is why it's opt-in (and why the web app
+    /// doesn't use one). The same table can be shared by several solvers with the same words.
+    ///
+    /// An empty table (see ColoringTable::new) only fills the rows of the guesses scored while at
+    /// least half of the words are possible answers, later guesses reuse the rows which were
+    /// already filled.
+    ///
+    pub fn set_coloring_table(&mut self, coloring_table: Option<Arc<ColoringTable>>) {
+        if let Some(table) = &coloring_table {
+            assert_eq!(
+                table.len(),
+                self.bank.len(),
+                "coloring table must be built from the solver's words"
+            );
+        }
+        self.coloring_table = coloring_table;
+    }
+
+    ///
+    /// Loads the full ColoringTable from the cache file (building & saving it if needed) and uses
+    /// it for scoring. This is meant for the native tools which make a lot of recommendations.
+    /// Does nothing for word sizes which a ColoringTable doesn't support.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_coloring_table(&mut self) {
+        if !ColoringTable::supports_word_size(self.config.word_size()) {
+            return;
+        }
+
+        let table = ColoringTable::load_or_build(self.bank.words(), COLORING_TABLE_CACHE_FILE);
+        self.set_coloring_table(Some(Arc::new(table)));
+    }
+
+    ///
+    /// How the host picks the answer (see HostModel)
+    ///
+    pub fn host_model(&self) -> HostModel {
+        self.host_model
+    }
+
+    ///
+    /// Sets how the host picks the answer. Against an Adversarial host (like Absurdle), guesses are
+    /// recommended by how quickly they force a win, instead of by the scoring strategy.
+    ///
+    pub fn set_host_model(&mut self, host_model: HostModel) {
+        self.host_model = host_model;
+    }
+
+    ///
+    /// How many tiles of each row the host may lie about (see LieModel)
+    ///
+    pub fn lie_model(&self) -> LieModel {
+        self.lies.model()
+    }
+
+    ///
+    /// Sets how many tiles of each row the host may lie about (like in Fibble), which changes the
+    /// words that are still possible. Lookahead & the endgame search assume honest colorings, so
+    /// neither is used while the host may lie.
+    ///
+    pub fn set_lie_model(&mut self, lie_model: LieModel) {
+        if lie_model != self.lie_model() {
+            self.lies = LieTable::new(lie_model, self.config.word_size());
+            self.recompute_from_guesses();
+        }
+    }
+
+    ///
+    /// The tiles which were most likely lies, weighing each tile by the probability of the
+    /// answers which would make it a lie (see likely_lies). Nothing is returned when the host is
+    /// honest.
+    ///
+    pub fn likely_lies(&self) -> Vec<LikelyLie> {
+        let max_lies = self.lie_model().max_lies();
+        if max_lies == 0 {
+            return Vec::new();
         }
 
-        // there are no None items in self.guesses, therefore we cannot store a new guess, and
-        // turns have been exhausted
-        None
+        let guesses: Vec<&Guess> = self.iter_guesses().collect();
+        likely_lies(
+            &guesses,
+            self.remaining_answers()
+                .map(|(id, probability)| (self.bank.word(id), probability)),
+            max_lies,
+        )
+    }
+
+    ///
+    /// Predicts the coloring an adversarial host would show for the guess, which is the coloring
+    /// shared by the most possible answers (see adversary_coloring). Returns None if the guess isn't
+    /// in the word bank, or if no possible answers remain.
+    ///
+    pub fn predict_adversary_coloring(&self, guess: &str) -> Option<Colorings> {
+        let id = self.bank.id(guess)?;
+        if !self.has_possible_guesses() {
+            return None;
+        }
+
+        let buckets = self.coloring_buckets_for(id, self.remaining_answers());
+        Some(adversary_coloring(self.config.word_size(), &buckets.sizes))
+    }
+
+    ///
+    /// Whether or not the word could still be the answer, given the guesses made so far
+    ///
+    pub fn is_possible_answer(&self, word: &str) -> bool {
+        self.bank
+            .id(word)
+            .is_some_and(|id| self.remaining_possibilities.contains(id))
     }
 
     ///
@@ -495,6 +1585,26 @@ impl<'a> Solver<'a> {
         self.num_guesses() == 0
     }
 
+    ///
+    /// The cached default state scores (keyed by ScoringStrategy::id) which were generated for the
+    /// current possible answers and word weights, if there are any. A different WordPrior (or
+    /// discounted past answers) changes the weights, so the cache can't be used with them.
+    ///
+    fn default_state_cache(&self) -> Option<&HashMap<String, Vec<ScoredCandidate<'a>>>> {
+        if !self.is_default_state() {
+            return None;
+        }
+
+        self.default_state_caches
+            .iter()
+            .find(|cache| {
+                cache.answers == self.remaining_possibilities
+                    && cache.weights == self.word_weights
+                    && cache.guess_count_model == self.guess_count_model
+            })
+            .map(|cache| &cache.guesses)
+    }
+
     ///
     /// Returns the highest scored guesses which remain. A maximum of K items are returned.
     ///
@@ -512,10 +1622,25 @@ impl<'a> Solver<'a> {
     {
         // an efficiency hack, mentioned a few times above... if we are in default state and we have
         // cached data available, then we should return that instead of computing it
-        if self.is_default_state() {
-            if let Some(dsd) = &self.default_state_guesses {
-                if dsd.len() >= K {
-                    return dsd.iter().copied().top_k(|item| item.score);
+        //
+        // The cached data contains the best guesses from both of the GuessPools, so only those
+        // in the selected pool are used. The cache only contains one step scores against a fair &
+        // honest host though, so it cannot be used with lookahead, an adversary or lies.
+        if self.lookahead.is_none()
+            && self.host_model == HostModel::Fair
+            && self.lie_model().is_honest()
+        {
+            let cache = self.default_state_cache();
+            if let Some(dsd) = cache.and_then(|cache| cache.get(self.scoring_strategy.id())) {
+                let cached: Vec<ScoredCandidate<'a>> = dsd
+                    .iter()
+                    .filter(|item| {
+                        self.guess_pool == GuessPool::AllowedWords || item.is_possible_answer
+                    })
+                    .copied()
+                    .collect();
+                if cached.len() >= K {
+                    return cached.into_iter().top_k(|item| item.score);
                 }
             }
         }
@@ -538,31 +1663,263 @@ impl<'a> Solver<'a> {
         [Option<ScoredCandidate<'a>>; K]: Default,
         [Option<Score>; K]: Default,
     {
-        self.remaining_possibilities
-            .iter()
-            .copied()
-            .map(|word| ScoredCandidate {
-                word,
-                score: self.score_guess(word),
+        let candidates: Vec<WordId> = match self.guess_pool {
+            GuessPool::RemainingPossibilities => self.remaining_possibilities.iter().collect(),
+            GuessPool::AllowedWords => self
+                .bank
+                .ids()
+                // every remaining possibility already follows the hard mode rules, but probe words
+                // might not
+                .filter(|id| self.hard_mode_violation(self.bank.word(*id)).is_none())
+                .collect(),
+        };
+
+        // an adversarial host decides the answer based on our guesses, so the probability of each
+        // answer doesn't matter, only how many turns it takes to leave the host with no way out
+        if self.host_model == HostModel::Adversarial {
+            return self.top_k_against_adversary(&candidates);
+        }
+
+        // once there are few enough possibilities, we can afford to find out exactly how many
+        // guesses each candidate needs (on average), which is what we really want to minimize
+        if self.is_endgame() {
+            let mut search = EndgameSearch::new(
+                self.remaining_answers()
+                    .map(|(id, probability)| (self.bank.word(id), probability)),
+            );
+
+            return self
+                .score_candidates(&candidates)
+                .into_iter()
+                .map(|candidate| ScoredCandidate {
+                    score: candidate
+                        .score
+                        .with_expected_guesses(search.expected_guesses_for(candidate.word)),
+                    ..candidate
+                })
+                .top_k(|item| item.score);
+        }
+
+        match self.lookahead.filter(|_| self.lie_model().is_honest()) {
+            // each chunk of candidates keeps it's own top K, and then those are merged. Both steps
+            // keep the first of any tied candidates, so this is the same as one big top_k
+            None => map_chunks(&candidates, SCORING_CHUNK_SIZE, |chunk| {
+                chunk
+                    .iter()
+                    .map(|id| self.score_candidate(*id))
+                    .top_k::<_, _, K>(|item| item.score)
             })
-            .top_k(|item| item.score)
+            .into_iter()
+            .flatten()
+            .top_k(|item| item.score),
+            Some(n) => {
+                // pick the n best candidates using the (cheap) one step score, then re-rank them
+                // using the (expensive) two step score. The rest still follow them (in one step
+                // order), so that K candidates are returned.
+                let mut best = self.score_candidates(&candidates);
+                best.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+                let rest = best.split_off(n.min(best.len()));
+                let reranked = map_chunks(&best, 1, |chunk| {
+                    chunk
+                        .iter()
+                        .map(|candidate| self.with_two_step_score(*candidate))
+                        .collect::<Vec<_>>()
+                })
+                .concat();
+                reranked
+                    .into_iter()
+                    .chain(rest)
+                    .top_k(|item| (item.score.two_step_info.is_some(), item.score))
+            }
+        }
+    }
+
+    ///
+    /// Picks the candidates which leave an adversarial host with the fewest possibilities, then
+    /// ranks them by the number of turns it takes to force a win (see AdversarySearch). The number
+    /// of turns is stored as the expected_guesses of each score, because against an adversary the
+    /// number of guesses isn't random.
+    ///
+    fn top_k_against_adversary<const K: usize>(
+        &self,
+        candidates: &[WordId],
+    ) -> TopK<ScoredCandidate<'a>, K>
+    where
+        [Option<ScoredCandidate<'a>>; K]: Default,
+        [Option<Score>; K]: Default,
+    {
+        let search = AdversarySearch::new(
+            self.remaining_possibilities
+                .iter()
+                .map(|id| self.bank.word(id)),
+            DEFAULT_ADVERSARY_SEARCH_WIDTH,
+        );
+
+        let mut best = self.score_candidates(candidates);
+        best.sort_by(|a, b| {
+            a.score
+                .worst_case
+                .cmp(&b.score.worst_case)
+                .then_with(|| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal))
+        });
+        best.truncate(ADVERSARY_SEARCH_CANDIDATES.max(K));
+        map_chunks(&best, 1, |chunk| {
+            // each chunk gets its own memo, so the chunks can be searched in parallel
+            let mut search = search.clone();
+            chunk
+                .iter()
+                .map(|candidate| ScoredCandidate {
+                    score: candidate
+                        .score
+                        .with_expected_guesses(search.turns_to_win(candidate.word) as WordleFloat),
+                    ..*candidate
+                })
+                .collect::<Vec<_>>()
+        })
+        .concat()
+        .into_iter()
+        .top_k(|item| item.score)
+    }
+
+    ///
+    /// Re-scores a candidate using the total expected info over two turns (see two_step_info)
+    ///
+    fn with_two_step_score(&self, candidate: ScoredCandidate<'a>) -> ScoredCandidate<'a> {
+        let two_step_info = self.two_step_info(candidate.id, &candidate.score);
+        let analysis = GuessAnalysis {
+            expected_info: two_step_info,
+            ..self.analyze_guess(candidate.id, candidate.is_possible_answer)
+        };
+
+        ScoredCandidate {
+            score: candidate.score.with_two_step_info(
+                two_step_info,
+                Score::new(self.scoring_strategy.as_ref(), &analysis),
+            ),
+            ..candidate
+        }
+    }
+
+    ///
+    /// Scores all of the candidates (in order), see map_chunks
+    ///
+    fn score_candidates(&self, candidates: &[WordId]) -> Vec<ScoredCandidate<'a>> {
+        map_chunks(candidates, SCORING_CHUNK_SIZE, |chunk| {
+            chunk
+                .iter()
+                .map(|id| self.score_candidate(*id))
+                .collect::<Vec<_>>()
+        })
+        .concat()
+    }
+
+    ///
+    /// Scores a word, and tags it with whether it could still be the answer
+    ///
+    fn score_candidate(&self, id: WordId) -> ScoredCandidate<'a> {
+        let is_possible_answer = self.remaining_possibilities.contains(id);
+        ScoredCandidate {
+            id,
+            word: self.bank.word(id),
+            score: self.score_guess(id, is_possible_answer),
+            is_possible_answer,
+        }
     }
 
     ///
     /// Computes a score for a given possible guess
     ///
-    fn score_guess(&self, guess: &'a str) -> Score {
-        // expected info in bits... explanation & definition below
-        let expected_info = self.expected_guess_info(guess);
+    fn score_guess(&self, guess: WordId, is_possible_answer: bool) -> Score {
+        let analysis = self.analyze_guess(guess, is_possible_answer);
+        Score::new(self.scoring_strategy.as_ref(), &analysis)
+    }
+
+    ///
+    /// Computes the top K guesses in the default state for each of the provided strategies, while
+    /// only analyzing each guess once. This is used to generate the cached default state data for
+    /// all of the built-in strategies (in gen_all_data.rs).
+    ///
+    /// The output is parallel to the strategies slice. Each strategy has the top K guesses from
+    /// every allowed word, followed by any of the top K possible answers which aren't among them
+    /// (so that the cache works for either GuessPool).
+    ///
+    pub fn compute_default_state_guesses<const K: usize>(
+        &self,
+        strategies: &[Box<dyn ScoringStrategy>],
+    ) -> Vec<Vec<ScoredCandidate<'a>>> {
+        assert!(self.is_default_state());
+        let ids: Vec<WordId> = self.bank.ids().collect();
+        let analyses: Vec<(WordId, GuessAnalysis)> =
+            map_chunks(&ids, SCORING_CHUNK_SIZE, |chunk| {
+                chunk
+                    .iter()
+                    .map(|id| {
+                        let is_possible_answer = self.remaining_possibilities.contains(*id);
+                        (*id, self.analyze_guess(*id, is_possible_answer))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .concat();
 
+        strategies
+            .iter()
+            .map(|strategy| {
+                let scored: Vec<ScoredCandidate<'a>> = analyses
+                    .iter()
+                    .map(|(id, analysis)| ScoredCandidate {
+                        id: *id,
+                        word: self.bank.word(*id),
+                        score: Score::new(strategy.as_ref(), analysis),
+                        is_possible_answer: self.remaining_possibilities.contains(*id),
+                    })
+                    .collect();
+
+                // the best K of every word, and the best K of the possible answers (which are the
+                // same words when there is no answer list)
+                let best: TopK<ScoredCandidate<'a>, K> =
+                    scored.iter().copied().top_k(|item| item.score);
+                let best_answers: TopK<ScoredCandidate<'a>, K> = scored
+                    .iter()
+                    .copied()
+                    .filter(|item| item.is_possible_answer)
+                    .top_k(|item| item.score);
+                let mut out: Vec<ScoredCandidate<'a>> = best.collect();
+                for item in best_answers {
+                    if out.iter().all(|other| other.id != item.id) {
+                        out.push(item);
+                    }
+                }
+                out
+            })
+            .collect()
+    }
+
+    ///
+    /// Computes everything the ScoringStrategy needs to know about a guess
+    ///
+    pub(crate) fn analyze_guess(&self, guess: WordId, is_possible_answer: bool) -> GuessAnalysis {
         // weight (not probability!) of the word
-        let weight = self
-            .word_weights
-            .get(guess)
-            .copied()
-            .unwrap_or(MIN_WORD_WEIGHT);
+        //
+        // a word which has been ruled out has no chance of being the answer, so it gets no bonus
+        // for being a common word, and must earn it's rank purely through expected info
+        let (weight, probability) = if is_possible_answer {
+            (
+                self.word_weights[guess as usize],
+                self.word_probability(guess),
+            )
+        } else {
+            (0.0, 0.0)
+        };
 
-        Score::new(expected_info, weight)
+        // expected info in bits (and the rest of the analysis) comes from the coloring buckets...
+        // explanation & definition on expected_guess_info
+        GuessAnalysis::new(
+            &self.coloring_buckets(guess),
+            weight,
+            probability,
+            self.entropy,
+            self.guess_count_model,
+        )
     }
 
     ///
@@ -599,53 +1956,161 @@ impl<'a> Solver<'a> {
     ///
     /// The "expected info" is therefore the sum of p * -p.log2() for all colorings.
     ///
-    fn expected_guess_info(&self, guess: &'a str) -> WordleFloat {
-        // This array holds a float  for each coloring which tracks the probability of it occurring.
-        #[allow(clippy::unnecessary_cast)]
-        let mut probabilities = [0.0 as WordleFloat; Colorings::NUM_STATES];
+    fn expected_guess_info(&self, guess: WordId) -> WordleFloat {
+        self.coloring_buckets(guess).expected_info()
+    }
 
-        // go through all possible answers that remain
-        for possible_answer in &self.remaining_possibilities {
-            // Figure out how probable this answer actually is...
-            // This is based on english word frequency data, and the sum of freq_weight_for applied
-            // across all possibilities should be 1.0
-            let weight = self.word_probability_for(possible_answer);
+    ///
+    /// Splits the remaining possibilities into buckets by the coloring the guess would produce
+    ///
+    fn coloring_buckets(&self, guess: WordId) -> ColoringBuckets {
+        // go through all possible answers that remain, and figure out how probable each one is...
+        // This is based on english word frequency data, and the sum of word_probability applied
+        // across all possibilities should be 1.0
+        let buckets = self.coloring_buckets_for(guess, self.remaining_answers());
 
-            // determine what coloring we'd get if we used guess & assumed answer=possible_answer
-            let coloring = Colorings::with_guess_answer(guess, possible_answer);
+        // ensure (in debug builds only) that the sum of all probabilities is (approximately) 1.0
+        debug_assert!((buckets.probabilities.iter().sum::<WordleFloat>() - 1.0).abs() < 0.0001);
 
-            // this converts the coloring to a unique index (it's like a base 3 number, see to_code)
-            let bucket_idx = coloring.to_code() as usize;
+        // when the host may lie, we see the coloring after the lies, not the true one
+        self.lies.spread(&buckets)
+    }
 
-            // we add the weight to the bucket because OR probabilities add (the chance of seeing
-            // a given coloring = chance of word A || chance of word B || ... when A, B, ...
-            // give that coloring)
-            probabilities[bucket_idx] += weight;
+    ///
+    /// Computes the total expected info over two turns, if we make this guess now and then make
+    /// the best possible follow-up guess.
+    ///
+    /// The remaining possibilities are split into buckets by the coloring that the guess would
+    /// produce. For each bucket, we find the follow-up guess with the highest expected info
+    /// (considering only the words in that bucket as possible answers). The result is the one step
+    /// expected info plus the average of the best follow-up info, weighted by the probability of
+    /// seeing each bucket.
+    ///
+    /// Buckets with a single word need no follow-up (we already know the answer), so they
+    /// contribute no additional info.
+    ///
+    fn two_step_info(&self, guess: WordId, score: &Score) -> WordleFloat {
+        let mut buckets: Vec<Vec<(WordId, WordleFloat)>> =
+            vec![Vec::new(); self.config.num_colorings()];
+        for (code, answer) in self.coloring_codes(guess, self.remaining_answers()) {
+            buckets[code as usize].push(answer);
         }
 
-        // ensure (in debug builds only) that the sum of all probabilities is (approximately) 1.0
-        debug_assert!((probabilities.iter().sum::<WordleFloat>() - 1.0).abs() < 0.0001);
+        let follow_up_info: WordleFloat = buckets
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, bucket)| bucket.len() > 1)
+            .map(|(code, bucket)| {
+                // normalize so that the probabilities within the bucket sum to 1.0
+                let bucket_probability: WordleFloat = bucket.iter().map(|(_, p)| p).sum();
+                for (_, p) in bucket.iter_mut() {
+                    *p /= bucket_probability;
+                }
+
+                // in hard mode, the follow up guess must also reuse the hints of this guess
+                let first_guess = self.hard_mode.then(|| Guess {
+                    word: GuessWord::new(self.bank.word(guess)),
+                    coloring: Colorings::from_code(code as ColoringCode, self.config.word_size())
+                        .expect("bucket index should be a coloring code"),
+                    expected_info: 0.0,
+                    entropy_delta: 0.0,
+                });
+                bucket_probability * self.best_follow_up_info(bucket, first_guess.as_ref())
+            })
+            .sum();
+
+        score.expected_info + follow_up_info
+    }
+
+    ///
+    /// Finds the highest expected info of any guess in the guess pool, assuming that the answer is
+    /// one of the (word id, probability) pairs in answers. In hard mode, the guess must follow the
+    /// rules given the guesses made so far and the first_guess (which was made before it).
+    ///
+    fn best_follow_up_info(
+        &self,
+        answers: &[(WordId, WordleFloat)],
+        first_guess: Option<&Guess>,
+    ) -> WordleFloat {
+        let info_for = |guess: WordId| {
+            self.coloring_buckets_for(guess, answers.iter().copied())
+                .expected_info()
+        };
 
-        // determine the average information gained
         #[allow(clippy::unnecessary_cast)]
-        probabilities
-            .iter()
-            // filter non-positive data (aka the 0s) because log2(0) is undefined
-            .filter(|v| *v > &(0.0 as WordleFloat))
-            .map(|v| v * -(v.log2()))
-            .sum()
+        let zero = 0.0 as WordleFloat;
+        match self.guess_pool {
+            GuessPool::RemainingPossibilities => answers
+                .iter()
+                .map(|(id, _)| info_for(*id))
+                .fold(zero, WordleFloat::max),
+            // the possible answers always follow the hard mode rules, but probe words might not
+            GuessPool::AllowedWords => self
+                .bank
+                .ids()
+                .filter(|id| {
+                    let word = self.bank.word(*id);
+                    self.hard_mode_violation(word).is_none()
+                        && first_guess.is_none_or(|g| g.hard_mode_violation(word).is_none())
+                })
+                .map(info_for)
+                .fold(zero, WordleFloat::max),
+        }
     }
 
     ///
-    /// Look up the probability of a given guess (not weight!).
+    /// The (word id, probability) of every word which could still be the answer
     ///
-    /// The word must be in remaining_possibilities (and therefore word_probabilities) or no
-    /// probability will be found.
+    fn remaining_answers(&self) -> impl Iterator<Item = (WordId, WordleFloat)> + '_ {
+        self.remaining_possibilities
+            .iter()
+            .map(|id| (id, self.word_probability(id)))
+    }
+
     ///
-    /// In a perfect world this would return an Option... but panics if no probability is found
+    /// Splits the (word id, probability) pairs into buckets by the coloring the guess would produce
     ///
-    fn word_probability_for(&self, guess: &'a str) -> WordleFloat {
-        self.word_probabilities[guess]
+    fn coloring_buckets_for(
+        &self,
+        guess: WordId,
+        answers: impl Iterator<Item = (WordId, WordleFloat)>,
+    ) -> ColoringBuckets {
+        ColoringBuckets::from_codes(
+            self.config.num_colorings(),
+            self.coloring_codes(guess, answers)
+                .map(|(code, (_, probability))| (code, probability)),
+        )
+    }
+
+    ///
+    /// Pairs each of the (word id, probability) answers with the code of the coloring that the guess
+    /// would get, if that word were the answer. The coloring_table is used if we have one.
+    ///
+    fn coloring_codes<'s>(
+        &'s self,
+        guess: WordId,
+        answers: impl Iterator<Item = (WordId, WordleFloat)> + 's,
+    ) -> impl Iterator<Item = (ColoringCode, (WordId, WordleFloat))> + 's {
+        // a row colors every word, so it's only worth filling while most of them could still be
+        // the answer (like in the default state). Later on, the rows which were filled are reused.
+        let row = self.coloring_table.as_ref().and_then(|table| {
+            if self.remaining_possibilities.len() * 2 >= table.len() {
+                Some(table.row(guess))
+            } else {
+                table.computed_row(guess)
+            }
+        });
+        let guess_word = self.bank.word(guess);
+
+        answers.map(move |answer| {
+            let code = match row {
+                Some(row) => row[answer.0 as usize] as ColoringCode,
+                None => {
+                    Colorings::with_guess_answer(guess_word, self.bank.word(answer.0)).to_code()
+                }
+            };
+            (code, answer)
+        })
     }
 
     ///
@@ -655,7 +2120,7 @@ impl<'a> Solver<'a> {
     where
         'a: 'b,
     {
-        iter_guesses(&self.guesses)
+        self.guesses.iter()
     }
 
     ///
@@ -663,190 +2128,731 @@ impl<'a> Solver<'a> {
     /// recalculating some data (such as word_weights) when we play another game
     ///
     pub fn reset(&mut self) {
-        self.guesses = [None; NUM_TURNS];
-        self.remaining_possibilities.clear();
-        self.remaining_possibilities.extend(&self.possible_words);
+        self.guesses.clear();
+        self.remaining_possibilities = self.possible_answers();
         self.recompute_word_probabilities();
     }
 }
 
 ///
-/// Returns whether or not the provided guesses allow the provided guess
-///
-/// This is external to the solver because it is used in only one place- recompute_possibilities
-/// which borrows solver '&mut self'
-///
-/// This function would be defined &self meaning recompute_possibilities would borrow self immutably
-/// & mutably, which is an error.
-///
-/// Therefore, we allow borrowing of the field &self.guesses (and passing that to this function)
-/// instead, which is not an error.
-///
-/// Think of it as constraining the scope of the immutable borrow to a single field, instead of
-/// borrowing the entire Solver struct to determine if the guess is allowed.
-///
-fn is_guess_allowed_by_existing_guesses(guesses: &[Option<Guess>], guess: &str) -> bool {
-    iter_guesses(guesses).all(|g| g.allows_other_guess(guess))
-}
-
-///
-/// Helper which takes any slice of Option<Guess> and iterates through references to the Guesses
-/// that have been made.
-///
-pub fn iter_guesses(guesses: &[Option<Guess>]) -> impl Iterator<Item = &Guess> {
-    guesses.iter().map_while(|item| item.as_ref())
-}
-
-///
-/// This function computes "weights" (not probabilities) for the possible_guesses.
-///
-/// Based on the 3blue1brown implementation, we base the weight on the word's rank.
-///
-/// An arbitrary line called N_COMMON(=2700) is defined. Words with lower ranks (ie; more common
-/// words with rank 0, 1, 2, etc) are considered common, whereas words with ranks higher than
-/// N_COMMON are considered uncommon.
-///
-/// A WIDTH is defined, and this is a unitless scaling factor.
+/// "weights" is the weight of seeing each word (indexed by WordId). These values do not sum to 1.0
 ///
-/// A value called "x" is calculated for each word. Imagine this as a position along a sigmoid curve.
-/// The most common word (rank=0) is given an "x" value = WIDTH, and words with lower ranks are
-/// linearly spaced such that the word with rank N_COMMON has an "x" value of 0. Words with ranks
-/// lower than N_COMMON continue the same linear spacing into negative numbers off to -inf.
+/// "words" is a subset of the words in the bank
 ///
-/// The "x" value is then passed into sigmoid so that it exists between (0.0, 1.0) for all words,
-/// and this is the "weight"
-///
-/// Finally, we use MIN_WORD_WEIGHT when no frequency data exists for a given word, or when the
-/// computed weight is below MIN_WORD_WEIGHT. When a word does not have frequency data, it is a
-/// fair assumption that it is extremely uncommon.
-///
-/// The constants N_COMMON and WIDTH can be tuned to possibly yield better results. Their values
-/// depend on the size of the allowed_words and frequency data file. If you use a different dataset
-/// for word frequency it is recommended to experiment and tune these constants to this new dataset.
+/// The output is a probability for every word (indexed by WordId) such that:
+///   * only words in the "words" WordSet have a non-zero probability
+///   * all probability values sum to (approximately) 1.0
 ///
-fn compute_word_weights(ordered_words: &[String]) -> impl Iterator<Item = (&str, WordleFloat)> {
-    // Implementation defines a few helper functions...
-    //
-    // * raw_compute_word_wight = actually do the computation, sometimes returning None when no
-    //                            data exists about a word
-    // * compute_word_weight = do the computation, but default to MIN_WORD_WEIGHT
-    //
-    #[inline]
-    fn raw_compute_word_weight(n_words: WordleFloat, rank: WordleFloat) -> Option<WordleFloat> {
-        const N_COMMON: WordleFloat = 2700.0;
-        const WIDTH: WordleFloat = 5.7;
-
-        let x = ((N_COMMON - rank) / n_words) * WIDTH;
-        let weight = sigmoid(x);
-
-        Some(if weight < MIN_WORD_WEIGHT {
-            MIN_WORD_WEIGHT
-        } else {
-            weight
-        })
-    }
-
-    #[inline]
-    fn compute_word_weight(n_words: WordleFloat, rank: usize) -> WordleFloat {
-        raw_compute_word_weight(n_words, rank as WordleFloat).unwrap_or(MIN_WORD_WEIGHT)
+fn compute_word_probabilities(words: &WordSet, weights: &[WordleFloat]) -> Vec<WordleFloat> {
+    // get weights for each of the words provided, and sum that up, so we can perform normalization
+    let total: WordleFloat = words.iter().map(|id| weights[id as usize]).sum();
+    // go through all the words (again) and divide each weight by the sum, producing a probability
+    let mut out = vec![0.0; weights.len()];
+    for id in words.iter() {
+        out[id as usize] = weights[id as usize] / total;
     }
-
-    let n_words = ordered_words.len() as WordleFloat;
-
-    ordered_words
-        .iter()
-        .map(|w| w.as_str())
-        .enumerate()
-        .map(move |(idx, w)| (w, compute_word_weight(n_words, idx)))
+    out
 }
 
 ///
-/// "weights" is a mapping from possible guesses -> weight of seeing that guess. These values do not
-/// sum to 1.0
+/// The entropy (in bits) of a probability distribution, the sum of p * -p.log2() for all p
 ///
-/// "words" is a subset of the keys from "weights"
-///
-/// The output is (word, probability) pairs such that:
-///   * only words in the "words" HashSet are emitted
-///   * all probability values sum to (approximately) 1.0
-///
-fn compute_word_probabilities<'a: 'b, 'b>(
-    words: &'b HashSet<&'a str>,
-    weights: &'b HashMap<&'a str, WordleFloat>,
-) -> impl Iterator<Item = (&'a str, WordleFloat)> + 'b {
-    // get weights for each of the words provided, and sum that up, so we can perform normalization
-    let total: WordleFloat = words.iter().map(|w| weights[w]).sum();
-    // go through all the words (again) and divide each weight by the sum, producing a probability
-    words.iter().map(move |w| (*w, weights[w] / total))
+fn compute_entropy(probabilities: &[WordleFloat]) -> WordleFloat {
+    probabilities
+        .iter()
+        .filter(|p| **p > 0.0)
+        .map(|p| p * -(p.log2()))
+        .sum()
 }
 
 ///
 /// The cached default state data is stored as &[DefaultStateEntry] and this function helps convert
-/// that to Vec<ScoredCandidate<'a>>.
+/// that to Vec<ScoredCandidate<'a>> for each strategy.
 ///
-fn compute_default_state_guesses<'a: 'b, 'b>(
-    words: &'b HashSet<&'a str>,
-    supplied_data: &'b [DefaultStateEntry],
-) -> impl Iterator<Item = ScoredCandidate<'a>> + 'b {
+fn compute_default_state_guesses<'a>(
+    bank: &WordBank<'a>,
+    possible_answers: &WordSet,
+    supplied_data: &[DefaultStateEntry],
+) -> HashMap<String, Vec<ScoredCandidate<'a>>> {
+    let mut out: HashMap<String, Vec<ScoredCandidate<'a>>> = HashMap::new();
     // go through the linear data from the text-file
-    supplied_data.iter().map(|entry| {
-        // find the correct &str from the 'words' HashSet
-        let word = *words
-            .iter()
-            .find(|item| *item == &entry.word)
+    for entry in supplied_data {
+        // find the id of the word in the bank
+        let id = bank
+            .id(&entry.word)
             .expect("default state data should contain possible words only");
 
         // create the score
         let score = Score {
             abs: entry.score,
+            tie_break: entry.tie_break,
             expected_info: entry.expected_info,
             weight: entry.weight,
+            worst_case: entry.worst_case,
+            two_step_info: None,
+            expected_guesses: None,
         };
 
         // combine
-        ScoredCandidate { word, score }
-    })
+        out.entry(entry.strategy.clone())
+            .or_default()
+            .push(ScoredCandidate {
+                id,
+                word: bank.word(id),
+                score,
+                is_possible_answer: possible_answers.contains(id),
+            });
+    }
+
+    out
 }
 
 #[cfg(test)]
-mod tests {
-    use crate::wordle::{iter_guesses, Guess};
+pub(crate) mod tests {
+    use crate::wordle::{
+        AnswerDate, AnswerPool, Coloring, ColoringTable, Colorings, GameConfig, GuessCountModel,
+        GuessPool, InflectedWord, Inflection, PastAnswer, RankSigmoidPrior, RepeatPolicy, Solver,
+        SolverErr, UniformPrior, WordListErr, WORD_SIZE,
+    };
+    use std::sync::Arc;
+
+    /// A small set of words which mostly differ in the first letter, shared by the tests of the
+    /// other solvers
+    pub(crate) fn atch_words() -> Vec<String> {
+        [
+            "latch", "batch", "catch", "hatch", "match", "patch", "watch", "chomp", "bloke",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect()
+    }
 
     #[test]
-    fn test_guess_iterator() {
+    fn test_guess_pool_recommends_probe_words() {
         use crate::wordle::Coloring::*;
-        let example_guess = Guess {
-            word: [b'a', b'a', b'a', b'a', b'a'],
-            coloring: [Excluded, Excluded, Excluded, Excluded, Excluded].into(),
-            expected_info: 0.0,
-            entropy_delta: 0.0,
-        };
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver
+            .make_guess(
+                "latch",
+                [Excluded, Correct, Correct, Correct, Correct].into(),
+            )
+            .unwrap();
+        assert_eq!(solver.num_remaining_possibilities(), 6);
+
+        let remaining_only: Vec<_> = solver.top_k_guesses::<8>().collect();
+        assert_eq!(remaining_only.len(), 6);
+        assert!(remaining_only.iter().all(|c| c.is_possible_answer));
+
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        let all_words: Vec<_> = solver.top_k_guesses::<8>().collect();
+        assert_eq!(all_words.len(), 8);
+        assert_eq!(all_words[0].word, "chomp");
+        assert!(!all_words[0].is_possible_answer);
+        assert_eq!(all_words[0].score.weight, 0.0);
+        assert!(!all_words
+            .iter()
+            .any(|c| c.word == "latch" && c.is_possible_answer));
+    }
 
-        {
-            let guesses = [
-                Some(example_guess.clone()),
-                Some(example_guess.clone()),
-                None,
-                None,
-                None,
-                None,
-            ];
-            let data: Vec<Guess> = iter_guesses(&guesses).cloned().collect();
-            let expected = [example_guess.clone(), example_guess.clone()];
-            assert_eq!(&data, &expected, "should have exactly two guesses");
+    #[test]
+    fn test_answer_list_limits_possibilities() {
+        use crate::wordle::Coloring::*;
+        let words = atch_words();
+        let answers: Vec<String> = ["batch", "catch", "watch"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_answer_list(&answers);
+        assert_eq!(solver.num_total_possibilities(), 3);
+        assert!(!solver.is_possible_answer("latch"));
+
+        // words which can't be the answer can still be guessed
+        solver
+            .make_guess(
+                "latch",
+                [Excluded, Correct, Correct, Correct, Correct].into(),
+            )
+            .unwrap();
+        assert_eq!(solver.num_remaining_possibilities(), 3);
+
+        solver.set_answer_pool(AnswerPool::AllowedWords);
+        assert_eq!(solver.num_remaining_possibilities(), 6);
+
+        solver.set_answer_pool(AnswerPool::AnswerList);
+        solver.reset();
+        assert_eq!(solver.num_remaining_possibilities(), 3);
+    }
+
+    #[test]
+    fn test_from_words() {
+        let words = atch_words();
+        let answers = vec!["catch".to_string(), "match".to_string()];
+        let mut weights = vec![1.0; words.len()];
+        weights[3] = 3.0;
+
+        let solver = Solver::from_words(
+            GameConfig::default(),
+            Some(&answers),
+            &words,
+            Some(&weights),
+        )
+        .unwrap();
+        assert_eq!(solver.num_total_possibilities(), 2);
+        // catch (id 2) has weight 1, match (id 4) has weight 1 too
+        assert_eq!(solver.word_probability(2), 0.5);
+
+        let solver =
+            Solver::from_words(GameConfig::default(), None, &words, Some(&weights)).unwrap();
+        assert_eq!(solver.num_total_possibilities(), words.len());
+        // hatch (id 3) has weight 3, the other 8 words have weight 1
+        assert!((solver.word_probability(3) - 3.0 / 11.0).abs() < 1e-6);
+
+        let unknown = vec!["crane".to_string()];
+        assert_eq!(
+            Solver::from_words(GameConfig::default(), Some(&unknown), &words, None).err(),
+            Some(WordListErr::UnknownAnswer("crane".to_string()))
+        );
+        assert_eq!(
+            Solver::from_words(GameConfig::default(), None, &words, Some(&weights[1..])).err(),
+            Some(WordListErr::WrongNumberOfWeights {
+                expected: words.len(),
+                got: words.len() - 1
+            })
+        );
+        let mut duplicated = words.clone();
+        duplicated.push("latch".to_string());
+        assert_eq!(
+            Solver::from_words(GameConfig::default(), None, &duplicated, None).err(),
+            Some(WordListErr::DuplicateWord("latch".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_word_prior() {
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        assert_eq!(solver.word_prior().name(), "rank sigmoid");
+
+        // with so few words, every word is "common" using the default parameters
+        solver.set_word_prior(Box::new(RankSigmoidPrior {
+            n_common: 4.0,
+            width: 5.7,
+        }));
+        let common_probability = solver.word_probability(0);
+        assert!(common_probability > solver.word_probability(8));
+
+        // a steeper sigmoid makes the common words even more likely
+        solver.set_word_prior(Box::new(RankSigmoidPrior {
+            n_common: 4.0,
+            width: 50.0,
+        }));
+        assert!(solver.word_probability(0) > common_probability);
+
+        solver.set_word_prior(Box::new(UniformPrior));
+        assert_eq!(solver.word_probability(0), solver.word_probability(8));
+    }
+
+    #[test]
+    fn test_repeat_policy_for_past_answers() {
+        let words = atch_words();
+        let past_answers: Vec<PastAnswer> = [("2022-01-01", "latch"), ("2022-03-01", "batch")]
+            .iter()
+            .map(|(date, word)| PastAnswer {
+                date: date.parse().unwrap(),
+                word: word.to_string(),
+            })
+            .collect();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_past_answers(&past_answers);
+        assert_eq!(
+            solver.latest_past_answer_date(),
+            Some(AnswerDate::new(2022, 3, 1).unwrap())
+        );
+        let latch_probability = solver.word_probability(0);
+
+        // only the answers on or before the cutoff are affected
+        let cutoff = AnswerDate::new(2022, 2, 1).unwrap();
+        solver.set_repeat_policy(RepeatPolicy::Exclude { cutoff });
+        assert_eq!(solver.num_remaining_possibilities(), words.len() - 1);
+        assert!(!solver.is_possible_answer("latch"));
+        assert!(solver.is_possible_answer("batch"));
+
+        solver.set_repeat_policy(RepeatPolicy::Discount {
+            cutoff,
+            factor: 0.1,
+        });
+        assert_eq!(solver.num_remaining_possibilities(), words.len());
+        assert!(solver.word_probability(0) < latch_probability);
+
+        solver.set_repeat_policy(RepeatPolicy::Allow);
+        assert_eq!(solver.word_probability(0), latch_probability);
+    }
+
+    #[test]
+    fn test_inflection_penalty() {
+        let words: Vec<String> = ["liked", "likes", "crane", "slate"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let inflections = [
+            InflectedWord {
+                word: "liked".to_string(),
+                inflection: Inflection::PastTense,
+            },
+            InflectedWord {
+                word: "likes".to_string(),
+                inflection: Inflection::Plural,
+            },
+        ];
+
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_word_prior(Box::new(UniformPrior));
+        solver.set_inflections(&inflections);
+        assert_eq!(solver.inflection("liked"), Some(Inflection::PastTense));
+        assert_eq!(solver.inflection("likes"), Some(Inflection::Plural));
+        assert_eq!(solver.inflection("crane"), None);
+        assert!(solver.word_probability(0) < solver.word_probability(2));
+
+        // the inflections are still possible answers, just less likely
+        assert_eq!(solver.num_remaining_possibilities(), words.len());
+        solver.set_inflection_penalty(1.0);
+        assert_eq!(solver.word_probability(0), solver.word_probability(2));
+    }
+
+    #[test]
+    fn test_lookahead_reranks_best_candidates() {
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_endgame_threshold(None);
+        solver.set_lookahead(Some(4));
+
+        let ranked: Vec<_> = solver.top_k_guesses::<9>().collect();
+        assert_eq!(
+            ranked.len(),
+            9,
+            "the other candidates follow the re-ranked ones"
+        );
+        let (reranked, rest) = ranked.split_at(4);
+        assert!(rest.iter().all(|c| c.score.two_step_info.is_none()));
+        for pair in reranked.windows(2).chain(rest.windows(2)) {
+            assert!(pair[0].score.abs >= pair[1].score.abs);
         }
 
-        {
-            let guesses = [
-                None,
-                Some(example_guess.clone()),
-                Some(example_guess.clone()),
-                None,
-                None,
-                None,
-            ];
-            let count = iter_guesses(&guesses).count();
-            assert_eq!(count, 0, "even though there are some guesses, they must be in order, and the first is None therefore there are no guesses, so the count should be 0... got {}", count);
+        for candidate in reranked {
+            let two_step_info = candidate.score.two_step_info.unwrap();
+            assert!(two_step_info >= candidate.score.expected_info);
+            // it is impossible to gain more information than the puzzle contains
+            assert!(two_step_info <= solver.remaining_entropy() + 0.000001);
+        }
+    }
+
+    #[test]
+    fn test_lookahead_follow_ups_obey_hard_mode() {
+        use crate::wordle::Coloring::*;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_endgame_threshold(None);
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        solver.set_lookahead(Some(9));
+        solver
+            .make_guess(
+                "latch",
+                [Excluded, Correct, Correct, Correct, Correct].into(),
+            )
+            .unwrap();
+
+        let batch_two_step_info = |solver: &Solver| {
+            solver
+                .top_k_guesses::<9>()
+                .find(|c| c.word == "batch")
+                .and_then(|c| c.score.two_step_info)
+                .unwrap()
+        };
+        let normal = batch_two_step_info(&solver);
+        // "chomp" would be the best follow up, but it doesn't reuse the hints
+        solver.set_hard_mode(true);
+        assert!(batch_two_step_info(&solver) < normal);
+    }
+
+    #[test]
+    fn test_endgame_ranks_by_expected_guesses() {
+        use crate::wordle::Coloring::*;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver
+            .make_guess(
+                "latch",
+                [Excluded, Correct, Correct, Correct, Correct].into(),
+            )
+            .unwrap();
+        assert!(solver.is_endgame());
+
+        let best = solver.top_k_guesses::<1>().next().unwrap();
+        let expected_guesses = best.score.expected_guesses.unwrap();
+        assert!((expected_guesses - 3.5).abs() < 0.001);
+
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        let best = solver.top_k_guesses::<1>().next().unwrap();
+        assert_eq!(best.word, "chomp");
+        assert!((best.score.expected_guesses.unwrap() - 2.5).abs() < 0.001);
+
+        solver.set_endgame_threshold(Some(5));
+        assert!(!solver.is_endgame());
+        let best = solver.top_k_guesses::<1>().next().unwrap();
+        assert_eq!(best.score.expected_guesses, None);
+    }
+
+    #[test]
+    fn test_endgame_only_for_strategies_which_want_it() {
+        use crate::wordle::Coloring::*;
+        use crate::wordle::*;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        assert_eq!(solver.endgame_threshold(), Some(DEFAULT_ENDGAME_THRESHOLD));
+        solver
+            .make_guess(
+                "latch",
+                [Excluded, Correct, Correct, Correct, Correct].into(),
+            )
+            .unwrap();
+        assert!(solver.is_endgame());
+
+        solver.set_scoring_strategy(Box::new(MostLikely));
+        assert!(!solver.is_endgame());
+        let best = solver.top_k_guesses::<1>().next().unwrap();
+        assert_eq!(best.word, "batch");
+        assert_eq!(best.score.expected_guesses, None);
+        assert_eq!(best.score.abs, solver.word_probability(best.id));
+
+        solver.set_scoring_strategy(Box::new(ExpectedGuesses));
+        assert!(solver.is_endgame());
+    }
+
+    #[test]
+    fn test_minimax_keeps_worst_case_in_endgame() {
+        use crate::wordle::*;
+        let words: Vec<String> = [
+            "frank", "grand", "grant", "brand", "drank", "prank", "trans", "krang", "brant",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        assert!(solver.is_endgame());
+        let fewest_guesses = solver.top_k_guesses::<1>().next().unwrap();
+        assert_eq!(fewest_guesses.word, "grand");
+        assert_eq!(fewest_guesses.score.worst_case, 4);
+
+        solver.set_scoring_strategy(Box::new(Minimax::default()));
+        assert!(!solver.is_endgame());
+        let best = solver.top_k_guesses::<1>().next().unwrap();
+        assert_eq!(best.word, "drank");
+        assert_eq!(best.score.worst_case, 3);
+        assert_eq!(best.score.expected_guesses, None);
+    }
+
+    #[test]
+    fn test_scoring_strategies() {
+        use crate::wordle::Coloring::*;
+        use crate::wordle::*;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_endgame_threshold(None);
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        solver
+            .make_guess(
+                "latch",
+                [Excluded, Correct, Correct, Correct, Correct].into(),
+            )
+            .unwrap();
+
+        fn best_for<'a>(
+            solver: &mut Solver<'a>,
+            strategy: Box<dyn ScoringStrategy>,
+        ) -> ScoredCandidate<'a> {
+            solver.set_scoring_strategy(strategy);
+            solver.top_k_guesses::<1>().next().unwrap()
         }
+
+        assert_eq!(best_for(&mut solver, Box::new(Entropy)).word, "chomp");
+        assert_eq!(
+            best_for(&mut solver, Box::new(Minimax::default())).word,
+            "chomp"
+        );
+        assert_eq!(
+            best_for(&mut solver, Box::new(ExpectedRemaining)).word,
+            "chomp"
+        );
+        assert!(best_for(&mut solver, Box::new(MostLikely)).is_possible_answer);
+
+        let best = best_for(&mut solver, Box::new(Minimax::default()));
+        assert_eq!(best.score.abs, -3.0);
+        assert_eq!(best.score.worst_case, 3);
+    }
+
+    #[test]
+    fn test_coloring_table_gives_same_scores() {
+        use crate::wordle::Coloring::*;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        solver.set_endgame_threshold(None);
+        solver.set_lookahead(Some(3));
+        solver
+            .make_guess(
+                "bloke",
+                [Excluded, Excluded, Excluded, Excluded, Excluded].into(),
+            )
+            .unwrap();
+
+        let ranked = |solver: &Solver| {
+            solver
+                .top_k_guesses::<9>()
+                .map(|c| (c.word.to_string(), c.score.abs, c.score.two_step_info))
+                .collect::<Vec<_>>()
+        };
+
+        // the table is opt-in, because it can use a lot of memory
+        assert!(solver.coloring_table().is_none());
+        let without_table = ranked(&solver);
+
+        let lazy_table = Arc::new(ColoringTable::new(&words));
+        solver.set_coloring_table(Some(lazy_table.clone()));
+        assert_eq!(without_table, ranked(&solver));
+        // 7 of the 9 words are still possible, so the rows were filled while scoring
+        assert!(lazy_table.computed_row(0).is_some());
+
+        let full_table = ColoringTable::new(&words);
+        full_table.fill();
+        solver.set_coloring_table(Some(Arc::new(full_table)));
+        assert_eq!(without_table, ranked(&solver));
+    }
+
+    #[test]
+    fn test_expected_guesses_to_solve() {
+        use crate::wordle::Coloring::Correct;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_guess_count_model(GuessCountModel {
+            coefficients: [1.0, 0.5, 0.0],
+        });
+
+        let entropy = solver.remaining_entropy();
+        let expected = solver.expected_guesses_to_solve().unwrap();
+        assert!((expected - (1.0 + 0.5 * entropy)).abs() < 0.000001);
+
+        solver
+            .make_guess("watch", [Correct; WORD_SIZE].into())
+            .unwrap();
+        assert!(solver.is_solved());
+        assert_eq!(solver.expected_guesses_to_solve(), None);
+    }
+
+    #[test]
+    fn test_guess_count_model_changes_expected_guesses_ranking() {
+        use crate::wordle::Coloring::*;
+        use crate::wordle::ExpectedGuesses;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_endgame_threshold(None);
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        solver.set_scoring_strategy(Box::new(ExpectedGuesses));
+        solver
+            .make_guess(
+                "latch",
+                [Excluded, Correct, Correct, Correct, Correct].into(),
+            )
+            .unwrap();
+
+        // when the entropy left doesn't cost any guesses, only the chance of winning now matters
+        solver.set_guess_count_model(GuessCountModel {
+            coefficients: [1.0, 0.0, 0.0],
+        });
+        let best = solver.top_k_guesses::<1>().next().unwrap();
+        assert_eq!(best.word, "batch");
+
+        // but when it's expensive, information matters more
+        solver.set_guess_count_model(GuessCountModel {
+            coefficients: [1.0, 10.0, 0.0],
+        });
+        let best = solver.top_k_guesses::<1>().next().unwrap();
+        assert_eq!(best.word, "chomp");
+    }
+
+    #[test]
+    fn test_undo_and_replace_guess() {
+        let words = atch_words();
+        let play = |guesses: &[(&str, &str)]| {
+            let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+            for (guess, answer) in guesses {
+                solver
+                    .make_guess(guess, Colorings::with_guess_answer(guess, answer))
+                    .unwrap();
+            }
+            solver
+        };
+        let summarize = |solver: &Solver| {
+            let remaining: Vec<_> = solver.remaining_possibilities().iter().collect();
+            let deltas: Vec<_> = solver.iter_guesses().map(|g| g.entropy_delta).collect();
+            (remaining, deltas, solver.remaining_entropy())
+        };
+
+        let mut solver = play(&[("chomp", "watch"), ("batch", "watch")]);
+        assert_eq!(
+            solver.undo_last_guess().map(|g| g.word.to_string()),
+            Some("batch".to_string()),
+            "should return the guess which was removed"
+        );
+        assert_eq!(summarize(&solver), summarize(&play(&[("chomp", "watch")])));
+
+        // editing the first guess must also update the entropy_delta of the guess after it
+        solver
+            .make_guess("batch", Colorings::with_guess_answer("batch", "watch"))
+            .unwrap();
+        solver
+            .replace_guess(0, "match", Colorings::with_guess_answer("match", "watch"))
+            .unwrap();
+        assert_eq!(
+            summarize(&solver),
+            summarize(&play(&[("match", "watch"), ("batch", "watch")]))
+        );
+
+        // the replacement solves the puzzle, so the guess after it can't be made & nothing changes
+        let before = summarize(&solver);
+        assert!(matches!(
+            solver.replace_guess(0, "match", [Coloring::Correct; WORD_SIZE].into()),
+            Err(SolverErr::AlreadySolved)
+        ));
+        assert!(matches!(
+            solver.replace_guess(2, "match", [Coloring::Correct; WORD_SIZE].into()),
+            Err(SolverErr::NoSuchGuess(2))
+        ));
+        assert_eq!(summarize(&solver), before);
+
+        while solver.undo_last_guess().is_some() {}
+        assert_eq!(solver.num_remaining_possibilities(), words.len());
+    }
+
+    #[test]
+    fn test_diagnose_contradiction() {
+        use crate::wordle::Coloring::*;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+
+        // the answer is watch, but the h was accidentally left gray
+        solver
+            .make_guess(
+                "chomp",
+                [Misplaced, Excluded, Excluded, Excluded, Excluded].into(),
+            )
+            .unwrap();
+        assert!(!solver.has_possible_guesses());
+
+        let diagnoses = solver.diagnose_contradiction::<4>();
+        let answers: Vec<_> = diagnoses.iter().map(|d| d.answer).collect();
+        assert_eq!(answers, vec!["latch", "batch", "hatch", "watch"]);
+        assert!(diagnoses.iter().all(|d| d.corrections.len() == 1));
+        assert_eq!(diagnoses[0].to_string(), "tile 2 of guess 1 to be yellow");
+
+        solver.apply_diagnosis(&diagnoses[0]).unwrap();
+        assert_eq!(solver.num_remaining_possibilities(), 4);
+        assert_eq!(
+            solver.iter_guesses().next().unwrap().coloring,
+            Colorings::with_guess_answer("chomp", "watch")
+        );
+    }
+
+    #[test]
+    fn test_make_guess_rejects_impossible_coloring() {
+        use crate::wordle::Coloring::*;
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+
+        // the second h can't be yellow when the first h is gray
+        let coloring = [Excluded, Misplaced, Excluded, Excluded, Misplaced].into();
+        assert!(matches!(
+            solver.make_guess("hatch", coloring),
+            Err(SolverErr::ImpossibleColoring(c)) if c == coloring
+        ));
+        assert_eq!(solver.num_guesses(), 0);
+    }
+
+    #[test]
+    fn test_hard_mode_rejects_unused_hints() {
+        use crate::wordle::Coloring::*;
+        use crate::wordle::{HardModeViolation, SolverErr};
+        let words = atch_words();
+        let mut solver = Solver::with_ordered_words(GameConfig::default(), &words, None);
+        solver.set_hard_mode(true);
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        solver
+            .make_guess(
+                "chomp",
+                [Misplaced, Misplaced, Excluded, Excluded, Excluded].into(),
+            )
+            .unwrap();
+
+        let violation = HardModeViolation::MissingMisplaced { letter: 'c' };
+        assert_eq!(solver.hard_mode_violation("latch"), None);
+        assert_eq!(solver.hard_mode_violation("bloke"), Some(violation));
+        assert_eq!(violation.to_string(), "guess must contain C");
+        assert!(solver.top_k_guesses::<9>().all(|c| c.word != "bloke"));
+
+        solver
+            .make_guess(
+                "latch",
+                [Excluded, Correct, Correct, Correct, Correct].into(),
+            )
+            .unwrap();
+        let violation = HardModeViolation::MissingCorrect {
+            idx: 1,
+            letter: 'a',
+        };
+        assert_eq!(
+            solver.make_guess("chomp", Colorings::new(WORD_SIZE)),
+            Err(SolverErr::HardModeViolation(violation))
+        );
+        assert_eq!(violation.to_string(), "2nd letter must be A");
+
+        solver.set_hard_mode(false);
+        assert_eq!(solver.hard_mode_violation("chomp"), None);
+    }
+
+    #[test]
+    fn test_word_size_and_turns_from_config() {
+        let words: Vec<String> = ["cart", "dart", "part", "wart", "mart", "kart", "tart"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        let play = |max_turns| {
+            let config = GameConfig::new(4, max_turns).unwrap();
+            let mut solver = Solver::with_ordered_words(config, &words, None);
+            assert_eq!(
+                solver.make_guess("chomp", Colorings::new(5)),
+                Err(SolverErr::InvalidGuess("chomp"))
+            );
+
+            let results: Vec<_> = words
+                .iter()
+                .map(|word| solver.make_guess(word, Colorings::with_guess_answer(word, "tart")))
+                .collect();
+            (solver, results)
+        };
+
+        // without a limit on the number of turns, we can keep guessing until we find the answer
+        let (solver, results) = play(None);
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(solver.num_guesses(), 7);
+        assert!(solver.is_solved());
+
+        // but normally the 7th guess isn't allowed
+        let (solver, results) = play(Some(6));
+        assert_eq!(results[6], Err(SolverErr::TurnsExhausted));
+        assert_eq!(solver.num_guesses(), 6);
+        assert!(!solver.can_guess());
+        assert_eq!(solver.num_remaining_possibilities(), 1);
     }
 }
@@ -0,0 +1,189 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{data::*, prelude::*};
+use std::fmt;
+
+///
+/// Estimates the expected number of guesses (including the guess which wins the game) that it
+/// takes to solve a puzzle which has some amount of entropy remaining.
+///
+/// This is the approach taken by 3blue1brown's second version of the solver. The expected info of
+/// a guess tells us how much entropy we expect to remain after making that guess, but it doesn't
+/// tell us how many more guesses that remaining entropy will cost us. This model fills that gap by
+/// mapping entropy (in bits) to guesses:
+///
+///   f(H) = c0 + c1 * H + c2 * H^2
+///
+/// The coefficients are fitted offline (see gen_guess_model.rs) from games simulated with the
+/// Solver, and are loaded from the embedded data when available.
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GuessCountModel {
+    pub coefficients: [WordleFloat; 3],
+}
+
+impl Default for GuessCountModel {
+    fn default() -> Self {
+        Self::FALLBACK
+    }
+}
+
+impl fmt::Display for GuessCountModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [c0, c1, c2] = self.coefficients;
+        write!(f, "{} {} {}", c0, c1, c2)
+    }
+}
+
+impl GuessCountModel {
+    ///
+    /// The coefficients used when no fitted model is embedded. These came from running
+    /// gen_guess_model against the default word list.
+    ///
+    pub const FALLBACK: Self = Self {
+        coefficients: [0.9127, 0.3069, -0.0075],
+    };
+
+    ///
+    /// The model fitted by gen_guess_model (from the embedded data), or the FALLBACK model if the
+    /// data was not generated.
+    ///
+    pub fn embedded() -> Self {
+        DATA.guess_count_model.unwrap_or_default()
+    }
+
+    ///
+    /// The expected number of guesses needed to solve a puzzle with the given entropy (in bits)
+    /// remaining. At least one guess is always needed, so this never returns less than 1.
+    ///
+    pub fn expected_guesses(&self, entropy: WordleFloat) -> WordleFloat {
+        let [c0, c1, c2] = self.coefficients;
+        let entropy = entropy.max(0.0);
+        (c0 + c1 * entropy + c2 * entropy * entropy).max(1.0)
+    }
+
+    ///
+    /// Fits a model to the provided samples using weighted least squares. Each sample is
+    /// (entropy, guesses taken to solve, weight).
+    ///
+    /// Returns None if there isn't enough variety in the samples to determine all 3 coefficients.
+    ///
+    pub fn fit(
+        samples: impl IntoIterator<Item = (WordleFloat, WordleFloat, WordleFloat)>,
+    ) -> Option<Self> {
+        // the normal equations (X^T W X) c = X^T W y, where each row of X is [1, H, H^2]
+        let mut xtx = [[0.0 as WordleFloat; 3]; 3];
+        let mut xty = [0.0 as WordleFloat; 3];
+        for (entropy, guesses, weight) in samples {
+            let row = [1.0, entropy, entropy * entropy];
+            for i in 0..3 {
+                for j in 0..3 {
+                    xtx[i][j] += weight * row[i] * row[j];
+                }
+                xty[i] += weight * row[i] * guesses;
+            }
+        }
+
+        solve_3x3(xtx, xty).map(|coefficients| Self { coefficients })
+    }
+}
+
+///
+/// Solves the linear system a * x = b using gaussian elimination (with partial pivoting). Returns
+/// None when the system is singular.
+///
+#[allow(clippy::needless_range_loop)]
+fn solve_3x3(mut a: [[WordleFloat; 3]; 3], mut b: [WordleFloat; 3]) -> Option<[WordleFloat; 3]> {
+    for col in 0..3 {
+        // swap the row with the largest value in this column into place
+        let pivot = (col..3).max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        // eliminate this column from all the rows below
+        for row in (col + 1)..3 {
+            let factor = a[row][col] / a[col][col];
+            for k in col..3 {
+                a[row][k] -= factor * a[col][k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    // back substitution
+    let mut x = [0.0 as WordleFloat; 3];
+    for row in (0..3).rev() {
+        let known: WordleFloat = ((row + 1)..3).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - known) / a[row][row];
+    }
+
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_recovers_quadratic() {
+        let expected = [1.2, 0.35, 0.02];
+        let samples = (0..40).map(|i| {
+            let entropy = i as WordleFloat * 0.3;
+            let guesses = expected[0] + expected[1] * entropy + expected[2] * entropy * entropy;
+            (entropy, guesses, 1.0)
+        });
+
+        let model = GuessCountModel::fit(samples).expect("should fit");
+        for (actual, expected) in model.coefficients.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 0.000001,
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_fit_needs_varied_samples() {
+        assert_eq!(
+            GuessCountModel::fit([(2.0, 3.0, 1.0), (2.0, 4.0, 1.0)]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expected_guesses_at_least_one() {
+        let model = GuessCountModel {
+            coefficients: [0.5, 0.1, 0.0],
+        };
+        assert_eq!(model.expected_guesses(0.0), 1.0);
+        assert_eq!(model.expected_guesses(-3.0), 1.0);
+        assert!((model.expected_guesses(10.0) - 1.5).abs() < 0.000001);
+    }
+}
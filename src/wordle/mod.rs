@@ -22,9 +22,28 @@
  * SOFTWARE.
  */
 
+mod absurdle;
+mod alphabet;
 mod color;
+mod coloring_table;
+mod config;
+mod constraints;
 mod data;
+mod diagnosis;
+mod endgame;
+mod fibble;
 mod game;
+mod guess_model;
+mod morphology;
+mod multi;
+mod past_answers;
 mod prelude;
+mod prior;
+mod scoring;
+mod word_bank;
 
-pub use {color::*, data::*, game::*, prelude::*};
+pub use {
+    absurdle::*, alphabet::*, color::*, coloring_table::*, config::*, constraints::*, data::*,
+    diagnosis::*, endgame::*, fibble::*, game::*, guess_model::*, morphology::*, multi::*,
+    past_answers::*, prelude::*, prior::*, scoring::*, word_bank::*,
+};
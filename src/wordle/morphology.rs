@@ -0,0 +1,320 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{alphabet::*, prelude::*};
+use std::fmt::{Display, Formatter};
+
+///
+/// How much less likely an inflected word is to be the answer (see Inflection). Daily puzzles
+/// almost never pick plurals or past tenses, but the word frequencies rank them like any other
+/// word.
+///
+pub const DEFAULT_INFLECTION_PENALTY: WordleFloat = 0.2;
+
+///
+/// The ways that a word can be an inflected form of another (base) word, which answer lists tend
+/// to avoid
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Inflection {
+    /// Ends in S, like "books", "boxes" or "flies" (also verbs like "bakes")
+    Plural,
+    /// Ends in ED, like "baked", "stopped" or "tried"
+    PastTense,
+}
+
+impl Inflection {
+    /// The inflection as a single byte (see from_byte)
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Inflection::Plural => 0,
+            Inflection::PastTense => 1,
+        }
+    }
+
+    /// Reads an inflection from the output of to_byte
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Inflection::Plural),
+            1 => Some(Inflection::PastTense),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Decides whether the (english) word looks like an inflection of some base word, where
+    /// is_known tells us which words exist (of any length).
+    ///
+    /// A base only counts when inflecting it gives back the word, so "james" isn't the plural of
+    /// "jam" (that would be "jams"). The base must also be known, or one of it's other inflections
+    /// must be known (see is_known_through_sibling).
+    ///
+    pub fn of(word: &str, is_known: impl Fn(&str) -> bool) -> Option<Self> {
+        let is_base = |base: &str, forms: Vec<String>| {
+            !base.is_empty()
+                && forms.iter().any(|form| form == word)
+                && (is_known(base) || is_known_through_sibling(base, word, &is_known))
+        };
+
+        if let Some(stem) = word.strip_suffix("ed") {
+            let mut bases = vec![format!("{}e", stem), stem.to_string()];
+            // stopped -> stop
+            let mut letters = stem.chars().rev();
+            if let (Some(a), Some(b)) = (letters.next(), letters.next()) {
+                if a == b {
+                    bases.push(stem[..stem.len() - a.len_utf8()].to_string());
+                }
+            }
+            // tried -> try
+            if let Some(rest) = stem.strip_suffix('i') {
+                bases.push(format!("{}y", rest));
+            }
+
+            if bases.iter().any(|base| is_base(base, past_tenses_of(base))) {
+                return Some(Inflection::PastTense);
+            }
+        }
+
+        if let Some(stem) = word.strip_suffix('s') {
+            // words like "class", "focus" and "basis" aren't plurals of anything
+            if stem.ends_with(['s', 'u', 'i']) {
+                return None;
+            }
+
+            let mut bases = vec![stem.to_string()];
+            // boxes -> box, flies -> fly
+            if let Some(rest) = stem.strip_suffix('e') {
+                bases.push(rest.to_string());
+                if let Some(rest) = rest.strip_suffix('i') {
+                    bases.push(format!("{}y", rest));
+                }
+            }
+
+            if bases.iter().any(|base| is_base(base, plurals_of(base))) {
+                return Some(Inflection::Plural);
+            }
+        }
+
+        None
+    }
+}
+
+impl Display for Inflection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Inflection::Plural => write!(f, "plural"),
+            Inflection::PastTense => write!(f, "past tense"),
+        }
+    }
+}
+
+///
+/// Words like "baked" and "bakes" are inflections of the same base, so either one is evidence that
+/// the base ("bake") exists even when only five letter words are known. This is only trusted for
+/// bases ending in a consonant and then an E or a Y (like "bake" or "try") though, since otherwise
+/// a shared stem is usually a coincidence: "greed" and "grees" don't come from "gree".
+///
+fn is_known_through_sibling(base: &str, word: &str, is_known: impl Fn(&str) -> bool) -> bool {
+    let mut letters = base.chars().rev();
+    let has_sibling_pattern = matches!(letters.next(), Some('e' | 'y'))
+        && letters.next().is_some_and(|letter| !is_vowel(letter));
+
+    has_sibling_pattern
+        && plurals_of(base)
+            .into_iter()
+            .chain(past_tenses_of(base))
+            .any(|form| form != word && is_known(&form))
+}
+
+fn is_vowel(letter: char) -> bool {
+    matches!(letter, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// The regular plurals (or third person verbs) of the base, like "books", "boxes" or "flies"
+fn plurals_of(base: &str) -> Vec<String> {
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| base.ends_with(suffix))
+    {
+        return vec![format!("{}es", base)];
+    }
+
+    if base.ends_with('o') {
+        return vec![format!("{}s", base), format!("{}es", base)];
+    }
+
+    match strip_consonant_y(base) {
+        Some(rest) => vec![format!("{}ies", rest)],
+        None => vec![format!("{}s", base)],
+    }
+}
+
+/// The regular past tenses of the base, like "baked", "tried", "added" or "stopped"
+fn past_tenses_of(base: &str) -> Vec<String> {
+    if base.ends_with('e') {
+        return vec![format!("{}d", base)];
+    }
+
+    if let Some(rest) = strip_consonant_y(base) {
+        return vec![format!("{}ied", rest)];
+    }
+
+    let mut out = vec![format!("{}ed", base)];
+    // a single consonant after a vowel may be doubled
+    let mut letters = base.chars().rev();
+    if let (Some(last), Some(before)) = (letters.next(), letters.next()) {
+        if !is_vowel(last) && !matches!(last, 'w' | 'x' | 'y') && is_vowel(before) {
+            out.push(format!("{}{}ed", base, last));
+        }
+    }
+    out
+}
+
+/// The base without it's final Y, when that Y follows a consonant (like "fly", but not "play")
+fn strip_consonant_y(base: &str) -> Option<&str> {
+    base.strip_suffix('y')
+        .filter(|rest| rest.chars().last().is_some_and(|letter| !is_vowel(letter)))
+}
+
+///
+/// A word which is an inflected form of another word (see Inflection)
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InflectedWord {
+    pub word: String,
+    pub inflection: Inflection,
+}
+
+///
+/// Finds the inflected words, using is_known to look up their base forms (see Inflection::of).
+/// The rules only make sense for english, so nothing is found for other alphabets.
+///
+pub fn find_inflections<'a>(
+    alphabet: &Alphabet,
+    words: impl IntoIterator<Item = &'a str>,
+    is_known: impl Fn(&str) -> bool,
+) -> Vec<InflectedWord> {
+    if *alphabet != Alphabet::ENGLISH {
+        return Vec::new();
+    }
+
+    words
+        .into_iter()
+        .filter_map(|word| {
+            Inflection::of(word, &is_known).map(|inflection| InflectedWord {
+                word: word.to_string(),
+                inflection,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordle::data::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_inflections() {
+        let known: HashSet<&str> = [
+            "book", "books", "box", "boxes", "fly", "flies", "stop", "stopped", "baked", "bakes",
+            "tries", "tried", "class", "focus", "lapse", "bleed", "speed", "greed", "grees", "jam",
+            "james",
+        ]
+        .into_iter()
+        .collect();
+        let is_known = |word: &str| known.contains(word);
+
+        assert_eq!(Inflection::of("books", is_known), Some(Inflection::Plural));
+        assert_eq!(Inflection::of("boxes", is_known), Some(Inflection::Plural));
+        assert_eq!(Inflection::of("flies", is_known), Some(Inflection::Plural));
+        assert_eq!(
+            Inflection::of("stopped", is_known),
+            Some(Inflection::PastTense)
+        );
+        // the base form "bake" isn't known, but the other inflection is
+        assert_eq!(
+            Inflection::of("baked", is_known),
+            Some(Inflection::PastTense)
+        );
+        assert_eq!(Inflection::of("bakes", is_known), Some(Inflection::Plural));
+        assert_eq!(
+            Inflection::of("tried", is_known),
+            Some(Inflection::PastTense)
+        );
+
+        // "grees" doesn't make "gree" a word, and the plural of "jam" is "jams"
+        for word in [
+            "class", "focus", "lapse", "bleed", "speed", "book", "greed", "james",
+        ] {
+            assert_eq!(Inflection::of(word, is_known), None, "{}", word);
+        }
+
+        for inflection in [Inflection::Plural, Inflection::PastTense] {
+            assert_eq!(
+                Inflection::from_byte(inflection.to_byte()),
+                Some(inflection)
+            );
+        }
+        assert!(find_inflections(&Alphabet::SPANISH, ["books"], is_known).is_empty());
+    }
+
+    #[test]
+    fn test_inflections_of_real_words() {
+        let read = |name: &str| {
+            std::fs::read_to_string(format!("{}{}", DATA_DIRECTORY, name))
+                .expect("should have the data file")
+        };
+        let allowed_words = read(ALLOWED_WORDS_FILE_NAME);
+        let base_words = read(BASE_WORDS_FILE_NAME);
+        let known: HashSet<&str> = allowed_words.lines().chain(base_words.lines()).collect();
+        let is_known = |word: &str| known.contains(word);
+
+        for word in [
+            "books", "cards", "words", "hands", "birds", "parts", "tries", "foxes",
+        ] {
+            assert_eq!(
+                Inflection::of(word, is_known),
+                Some(Inflection::Plural),
+                "{}",
+                word
+            );
+        }
+        for word in ["asked", "tried", "added", "baked", "freed", "ended"] {
+            assert_eq!(
+                Inflection::of(word, is_known),
+                Some(Inflection::PastTense),
+                "{}",
+                word
+            );
+        }
+        for word in [
+            "greed", "breed", "creed", "bleed", "speed", "steed", "james", "yours", "class",
+            "bonus",
+        ] {
+            assert_eq!(Inflection::of(word, is_known), None, "{}", word);
+        }
+    }
+}
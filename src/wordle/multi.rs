@@ -0,0 +1,478 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{
+    color::*, coloring_table::*, config::*, data::*, game::*, prelude::*, scoring::*, word_bank::*,
+};
+use std::sync::Arc;
+use thiserror::Error;
+
+///
+/// The number of turns usually allowed when solving num_boards boards at once. Each extra board
+/// gets one more turn, like Dordle (2 boards, 7 turns), Quordle (4 boards, 9 turns) and Octordle
+/// (8 boards, 13 turns).
+///
+pub fn multi_board_turns(num_boards: usize) -> usize {
+    NUM_TURNS - 1 + num_boards
+}
+
+///
+/// Solves several wordle puzzles (boards) at once, where every guess is made on all of the boards
+/// which haven't been solved yet (like Dordle, Quordle & Octordle).
+///
+/// Each board is tracked by its own Solver, which all share the same words. Boards are solved at
+/// different times, after which they stop receiving guesses, so a board may have fewer guesses
+/// than the others.
+///
+/// Guesses are scored by adding up the analysis of the guess on every unsolved board (see
+/// combine_analyses) and scoring that with the scoring strategy of the first board. With the
+/// default strategy this means the expected info (and weight) is summed across the boards.
+///
+pub struct MultiSolver<'a> {
+    boards: Vec<Solver<'a>>,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MultiSolverErr<'a> {
+    #[error("expected a coloring for each of the {expected} boards, got {got}")]
+    WrongNumberOfColorings { expected: usize, got: usize },
+    #[error("every board is already solved")]
+    AlreadySolved,
+    #[error("board {}: {err}", board + 1)]
+    Board { board: usize, err: SolverErr<'a> },
+}
+
+/// Implementation of Default uses the embedded data to construct a Quordle (4 board) solver
+impl Default for MultiSolver<'static> {
+    fn default() -> Self {
+        Self::with_num_boards(4)
+    }
+}
+
+impl MultiSolver<'static> {
+    ///
+    /// Uses the embedded data to construct a solver with num_boards boards, which each allow
+    /// multi_board_turns(num_boards) guesses. The boards don't have a ColoringTable, one can be
+    /// shared by all of them with set_coloring_table.
+    ///
+    pub fn with_num_boards(num_boards: usize) -> Self {
+        let config = GameConfig::new(WORD_SIZE, Some(multi_board_turns(num_boards)))
+            .expect("default word size is valid");
+        Self::new(
+            (0..num_boards)
+                .map(|_| Solver::with_config(config, &DATA))
+                .collect(),
+        )
+    }
+}
+
+impl<'a> MultiSolver<'a> {
+    ///
+    /// Creates a MultiSolver from one Solver per board. The solvers must all use the same words (in
+    /// the same order), and there must be at least one.
+    ///
+    pub fn new(boards: Vec<Solver<'a>>) -> Self {
+        assert!(!boards.is_empty(), "there must be at least one board");
+        let words = boards[0].word_bank().words();
+        assert!(
+            boards.iter().all(|b| b.word_bank().words() == words),
+            "every board must use the same words"
+        );
+        Self { boards }
+    }
+
+    pub fn num_boards(&self) -> usize {
+        self.boards.len()
+    }
+
+    ///
+    /// The Solver for each board, which can be used to inspect the state of that board
+    ///
+    pub fn boards(&self) -> &[Solver<'a>] {
+        &self.boards
+    }
+
+    ///
+    /// The indexes of the boards which haven't been solved yet (and so will receive the next guess)
+    ///
+    pub fn unsolved_boards(&self) -> impl Iterator<Item = usize> + '_ {
+        self.boards
+            .iter()
+            .enumerate()
+            .filter(|(_, board)| !board.is_solved())
+            .map(|(idx, _)| idx)
+    }
+
+    ///
+    /// Whether or not every board is solved
+    ///
+    pub fn is_solved(&self) -> bool {
+        self.boards.iter().all(|board| board.is_solved())
+    }
+
+    ///
+    /// Whether or not another guess can be made, which needs every unsolved board to be able to
+    /// accept it (see Solver::can_guess)
+    ///
+    pub fn can_guess(&self) -> bool {
+        !self.is_solved()
+            && self
+                .unsolved_boards()
+                .all(|board| self.boards[board].can_guess())
+    }
+
+    ///
+    /// Returns the number of guesses made so far (which is the number of guesses on any board which
+    /// isn't solved yet)
+    ///
+    pub fn num_guesses(&self) -> usize {
+        self.boards
+            .iter()
+            .map(|board| board.num_guesses())
+            .max()
+            .unwrap_or(0)
+    }
+
+    ///
+    /// Makes the guess on every unsolved board. There must be one coloring per board, but the
+    /// colorings for boards which are already solved are ignored.
+    ///
+    /// If any board rejects the guess, the guess isn't made on any of the boards.
+    ///
+    pub fn make_guess<'g>(
+        &mut self,
+        guess: &'g str,
+        colorings: &[Colorings],
+    ) -> Result<(), MultiSolverErr<'g>> {
+        if colorings.len() != self.boards.len() {
+            return Err(MultiSolverErr::WrongNumberOfColorings {
+                expected: self.boards.len(),
+                got: colorings.len(),
+            });
+        }
+
+        let unsolved: Vec<usize> = self.unsolved_boards().collect();
+        if unsolved.is_empty() {
+            return Err(MultiSolverErr::AlreadySolved);
+        }
+
+        for (n, board) in unsolved.iter().copied().enumerate() {
+            if let Err(err) = self.boards[board].make_guess(guess, colorings[board]) {
+                for made in &unsolved[..n] {
+                    self.boards[*made].undo_last_guess();
+                }
+
+                return Err(MultiSolverErr::Board { board, err });
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Removes the most recent guess from every board which received it. Returns the guess which
+    /// was removed from each board (None for boards which were solved before that guess, or when
+    /// no guesses have been made).
+    ///
+    pub fn undo_last_guess(&mut self) -> Vec<Option<Guess>> {
+        let num_guesses = self.num_guesses();
+        self.boards
+            .iter_mut()
+            .map(|board| {
+                if num_guesses > 0 && board.num_guesses() == num_guesses {
+                    board.undo_last_guess()
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Clears the guesses on every board
+    ///
+    pub fn reset(&mut self) {
+        for board in &mut self.boards {
+            board.reset();
+        }
+    }
+
+    ///
+    /// Sets the ColoringTable used for scoring on every board (see Solver::set_coloring_table).
+    /// The boards all share the one table, so it only costs as much memory as it would for a
+    /// single board.
+    ///
+    pub fn set_coloring_table(&mut self, coloring_table: Option<Arc<ColoringTable>>) {
+        for board in &mut self.boards {
+            board.set_coloring_table(coloring_table.clone());
+        }
+    }
+
+    ///
+    /// Loads the full ColoringTable once (see Solver::load_coloring_table) and shares it with
+    /// every board
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_coloring_table(&mut self) {
+        self.boards[0].load_coloring_table();
+        let coloring_table = self.boards[0].coloring_table().cloned();
+        self.set_coloring_table(coloring_table);
+    }
+
+    ///
+    /// Returns which words are considered when making recommendations
+    ///
+    pub fn guess_pool(&self) -> GuessPool {
+        self.boards[0].guess_pool()
+    }
+
+    ///
+    /// Sets which words are considered when making recommendations. With
+    /// GuessPool::RemainingPossibilities, any word which could be the answer on at least one
+    /// unsolved board is considered.
+    ///
+    pub fn set_guess_pool(&mut self, guess_pool: GuessPool) {
+        for board in &mut self.boards {
+            board.set_guess_pool(guess_pool);
+        }
+    }
+
+    ///
+    /// Returns the highest scored guesses, considering every unsolved board. A maximum of K items
+    /// are returned. A candidate is a possible answer if it could be the answer on any unsolved
+    /// board.
+    ///
+    pub fn top_k_guesses<const K: usize>(&self) -> TopK<ScoredCandidate<'a>, K>
+    where
+        [Option<ScoredCandidate<'a>>; K]: Default,
+        [Option<Score>; K]: Default,
+    {
+        let unsolved: Vec<&Solver<'a>> = self
+            .unsolved_boards()
+            .map(|board| &self.boards[board])
+            .collect();
+
+        // before any guesses are made every board is the same, so the (cached) recommendations for
+        // a single board are used instead of adding up identical analyses
+        if self.num_guesses() == 0 {
+            return self.boards[0].top_k_guesses();
+        }
+
+        let bank: &WordBank<'a> = self.boards[0].word_bank();
+        // the guess is made on every unsolved board, so it has to follow the hard mode rules of
+        // each of them (even the words which are possible on some board might not)
+        let candidates: Vec<WordId> = match self.guess_pool() {
+            GuessPool::RemainingPossibilities => bank
+                .ids()
+                .filter(|id| is_possible_on_any(&unsolved, *id))
+                .filter(|id| is_allowed_on_all(&unsolved, bank.word(*id)))
+                .collect(),
+            GuessPool::AllowedWords => bank
+                .ids()
+                .filter(|id| is_allowed_on_all(&unsolved, bank.word(*id)))
+                .collect(),
+        };
+
+        let strategy = self.boards[0].scoring_strategy();
+        map_chunks(&candidates, 64, |chunk| {
+            chunk
+                .iter()
+                .map(|id| {
+                    let analysis = combine_analyses(unsolved.iter().map(|board| {
+                        let is_possible_answer = board.remaining_possibilities().contains(*id);
+                        board.analyze_guess(*id, is_possible_answer)
+                    }));
+                    ScoredCandidate {
+                        id: *id,
+                        word: bank.word(*id),
+                        score: Score::new(strategy, &analysis),
+                        is_possible_answer: is_possible_on_any(&unsolved, *id),
+                    }
+                })
+                .top_k::<_, _, K>(|item| item.score)
+        })
+        .into_iter()
+        .flatten()
+        .top_k(|item| item.score)
+    }
+}
+
+fn is_possible_on_any(boards: &[&Solver], id: WordId) -> bool {
+    boards
+        .iter()
+        .any(|board| board.remaining_possibilities().contains(id))
+}
+
+fn is_allowed_on_all(boards: &[&Solver], word: &str) -> bool {
+    boards
+        .iter()
+        .all(|board| board.hard_mode_violation(word).is_none())
+}
+
+///
+/// Adds up the analysis of a guess on each board, so that the total can be scored like a single
+/// board. The info, weights, probabilities, expected remaining words & entropies are summed (so
+/// the probability is the expected number of boards solved by the guess), and the worst case is
+/// the worst case of any board.
+///
+fn combine_analyses(analyses: impl Iterator<Item = GuessAnalysis>) -> GuessAnalysis {
+    analyses
+        .reduce(|a, b| GuessAnalysis {
+            expected_info: a.expected_info + b.expected_info,
+            weight: a.weight + b.weight,
+            probability: a.probability + b.probability,
+            largest_bucket_size: a.largest_bucket_size.max(b.largest_bucket_size),
+            largest_bucket_probability: a
+                .largest_bucket_probability
+                .max(b.largest_bucket_probability),
+            expected_remaining: a.expected_remaining + b.expected_remaining,
+            remaining_entropy: a.remaining_entropy + b.remaining_entropy,
+            guess_count_model: a.guess_count_model,
+        })
+        .expect("at least one board must be unsolved")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordle::{game::tests::atch_words, Coloring::*};
+
+    /// Makes the guess with the colorings it would get on boards with the answers catch & watch
+    fn play<'a>(solver: &mut MultiSolver<'a>, guess: &'a str) -> Result<(), MultiSolverErr<'a>> {
+        let colorings: Vec<Colorings> = ["catch", "watch"]
+            .iter()
+            .map(|answer| Colorings::with_guess_answer(guess, answer))
+            .collect();
+        solver.make_guess(guess, &colorings)
+    }
+
+    #[test]
+    fn test_boards_solved_at_different_times() {
+        let words = atch_words();
+        let config = GameConfig::new(WORD_SIZE, Some(multi_board_turns(2))).unwrap();
+        let mut solver = MultiSolver::new(vec![
+            Solver::with_ordered_words(config, &words, None),
+            Solver::with_ordered_words(config, &words, None),
+        ]);
+        assert_eq!(
+            solver.make_guess("chomp", &[Colorings::new(WORD_SIZE)]),
+            Err(MultiSolverErr::WrongNumberOfColorings {
+                expected: 2,
+                got: 1
+            })
+        );
+        // a bad coloring on the second board means the guess isn't made on either board
+        let impossible = [Correct, Correct, Correct, Correct, Misplaced].into();
+        assert_eq!(
+            solver.make_guess("catch", &[Colorings::all_correct(WORD_SIZE), impossible]),
+            Err(MultiSolverErr::Board {
+                board: 1,
+                err: SolverErr::ImpossibleColoring(impossible)
+            })
+        );
+        assert_eq!(solver.num_guesses(), 0);
+
+        // chomp finds catch on the first board, but leaves {latch, batch, hatch, watch} on the
+        // second, where latch gives the most info
+        play(&mut solver, "chomp").unwrap();
+        let best: Vec<_> = solver.top_k_guesses::<9>().collect();
+        assert_eq!(best[0].word, "latch");
+        assert!(best
+            .iter()
+            .any(|c| c.word == "catch" && c.is_possible_answer));
+
+        play(&mut solver, "catch").unwrap();
+        assert!(solver.boards()[0].is_solved());
+        assert_eq!(solver.unsolved_boards().collect::<Vec<_>>(), vec![1]);
+
+        // only the second board receives guesses now
+        play(&mut solver, "watch").unwrap();
+        assert!(solver.is_solved());
+        assert!(!solver.can_guess());
+        assert_eq!(solver.boards()[0].num_guesses(), 2);
+        assert_eq!(solver.num_guesses(), 3);
+
+        let undone = solver.undo_last_guess();
+        assert!(undone[0].is_none());
+        assert_eq!(
+            undone[1].map(|g| g.word.to_string()),
+            Some("watch".to_string())
+        );
+        assert!(solver.can_guess());
+    }
+
+    #[test]
+    fn test_recommendations_follow_hard_mode_on_every_board() {
+        let words = atch_words();
+        let config = GameConfig::new(WORD_SIZE, Some(multi_board_turns(2))).unwrap();
+        let mut boards = vec![
+            Solver::with_ordered_words(config, &words, None),
+            Solver::with_ordered_words(config, &words, None),
+        ];
+        for board in &mut boards {
+            board.set_hard_mode(true);
+            board.set_guess_pool(GuessPool::AllowedWords);
+        }
+        let mut solver = MultiSolver::new(boards);
+
+        // chomp shows a green c on the first board, which only catch & chomp reuse
+        play(&mut solver, "chomp").unwrap();
+        let best: Vec<_> = solver.top_k_guesses::<9>().map(|c| c.word).collect();
+        assert_eq!(best.len(), 2);
+        assert!(best.contains(&"catch") && best.contains(&"chomp"));
+
+        solver.set_guess_pool(GuessPool::RemainingPossibilities);
+        let best: Vec<_> = solver.top_k_guesses::<9>().map(|c| c.word).collect();
+        assert_eq!(best, vec!["catch"]);
+    }
+
+    #[test]
+    fn test_boards_share_one_coloring_table() {
+        let words = atch_words();
+        let config = GameConfig::new(WORD_SIZE, Some(multi_board_turns(2))).unwrap();
+        let mut solver = MultiSolver::new(vec![
+            Solver::with_ordered_words(config, &words, None),
+            Solver::with_ordered_words(config, &words, None),
+        ]);
+        solver.set_guess_pool(GuessPool::AllowedWords);
+        assert!(solver.boards().iter().all(|b| b.coloring_table().is_none()));
+
+        play(&mut solver, "bloke").unwrap();
+        let scores = |solver: &MultiSolver| {
+            solver
+                .top_k_guesses::<9>()
+                .map(|c| (c.word.to_string(), c.score))
+                .collect::<Vec<_>>()
+        };
+        let without_table = scores(&solver);
+
+        let table = Arc::new(ColoringTable::new(&words));
+        solver.set_coloring_table(Some(table.clone()));
+        assert!(solver
+            .boards()
+            .iter()
+            .all(|b| Arc::ptr_eq(b.coloring_table().unwrap(), &table)));
+        assert_eq!(without_table, scores(&solver));
+    }
+}
@@ -0,0 +1,236 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// How much less likely a past answer is to be picked again when it's discounted (see
+/// RepeatPolicy::Discount)
+///
+pub const DEFAULT_REPEAT_DISCOUNT: WordleFloat = 0.1;
+
+///
+/// The date of a daily puzzle. Dates are written (and parsed) like 2021-06-19, and they're ordered
+/// from oldest to newest.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnswerDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AnswerDateErr {
+    #[error("the date '{0}' should look like YYYY-MM-DD")]
+    Malformed(String),
+    #[error("{0:04}-{1:02}-{2:02} is not a real date")]
+    NoSuchDate(u16, u8, u8),
+}
+
+impl AnswerDate {
+    /// The number of bytes used to store a date (see to_bytes)
+    pub const NUM_BYTES: usize = 4;
+
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, AnswerDateErr> {
+        if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+            return Err(AnswerDateErr::NoSuchDate(year, month, day));
+        }
+
+        Ok(Self { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The date as NUM_BYTES bytes: the year (little endian), then the month and the day
+    pub fn to_bytes(self) -> [u8; Self::NUM_BYTES] {
+        let [y0, y1] = self.year.to_le_bytes();
+        [y0, y1, self.month, self.day]
+    }
+
+    /// Reads a date from the output of to_bytes
+    pub fn from_bytes(bytes: [u8; Self::NUM_BYTES]) -> Result<Self, AnswerDateErr> {
+        let [y0, y1, month, day] = bytes;
+        Self::new(u16::from_le_bytes([y0, y1]), month, day)
+    }
+}
+
+impl FromStr for AnswerDate {
+    type Err = AnswerDateErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || AnswerDateErr::Malformed(s.to_string());
+        let mut parts = s.trim().split('-');
+        let mut next_part = || parts.next().ok_or_else(malformed);
+        let (year, month, day) = (next_part()?, next_part()?, next_part()?);
+        if parts.next().is_some() || year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return Err(malformed());
+        }
+
+        Self::new(
+            year.parse().map_err(|_| malformed())?,
+            month.parse().map_err(|_| malformed())?,
+            day.parse().map_err(|_| malformed())?,
+        )
+    }
+}
+
+impl Display for AnswerDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+///
+/// A word which was the answer of a daily puzzle, and the date of that puzzle
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PastAnswer {
+    pub date: AnswerDate,
+    pub word: String,
+}
+
+///
+/// Controls how likely a word is to be the answer again, after it was the answer of a daily puzzle
+/// (see PastAnswer).
+///
+/// The NYT doesn't repeat answers, so the words which were the answer on or before the cutoff
+/// (usually yesterday) can be ruled out, or just made less likely in case that ever changes.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum RepeatPolicy {
+    /// Past answers are as likely as any other word
+    #[default]
+    Allow,
+    /// Past answers from on or before the cutoff can't be the answer
+    Exclude { cutoff: AnswerDate },
+    /// The weight of past answers from on or before the cutoff is multiplied by the factor
+    Discount {
+        cutoff: AnswerDate,
+        factor: WordleFloat,
+    },
+}
+
+impl RepeatPolicy {
+    ///
+    /// What the weight of a word which was the answer on the date should be multiplied by, or None
+    /// when the word can't be the answer again
+    ///
+    pub fn weight_factor(&self, date: AnswerDate) -> Option<WordleFloat> {
+        match *self {
+            RepeatPolicy::Exclude { cutoff } if date <= cutoff => None,
+            RepeatPolicy::Discount { cutoff, factor } if date <= cutoff => Some(factor),
+            _ => Some(1.0),
+        }
+    }
+}
+
+impl Display for RepeatPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepeatPolicy::Allow => write!(f, "past answers can repeat"),
+            RepeatPolicy::Exclude { cutoff } => {
+                write!(f, "answers through {} can't repeat", cutoff)
+            }
+            RepeatPolicy::Discount { cutoff, factor } => write!(
+                f,
+                "answers through {} are {:.0}% as likely",
+                cutoff,
+                factor * 100.0
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_dates() {
+        let date: AnswerDate = "2024-02-29".parse().unwrap();
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(AnswerDate::from_bytes(date.to_bytes()), Ok(date));
+        assert!(date < "2024-03-01".parse().unwrap());
+        assert!(date > "2023-12-31".parse().unwrap());
+
+        assert_eq!(
+            "2023-02-29".parse::<AnswerDate>(),
+            Err(AnswerDateErr::NoSuchDate(2023, 2, 29))
+        );
+        assert!(matches!(
+            "2023-2-28".parse::<AnswerDate>(),
+            Err(AnswerDateErr::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_repeat_policy_weight_factor() {
+        let cutoff = AnswerDate::new(2022, 1, 1).unwrap();
+        let before = AnswerDate::new(2021, 6, 19).unwrap();
+        let after = AnswerDate::new(2022, 1, 2).unwrap();
+
+        assert_eq!(RepeatPolicy::Allow.weight_factor(before), Some(1.0));
+        assert_eq!(RepeatPolicy::Exclude { cutoff }.weight_factor(before), None);
+        assert_eq!(RepeatPolicy::Exclude { cutoff }.weight_factor(cutoff), None);
+        assert_eq!(
+            RepeatPolicy::Exclude { cutoff }.weight_factor(after),
+            Some(1.0)
+        );
+
+        let discount = RepeatPolicy::Discount {
+            cutoff,
+            factor: DEFAULT_REPEAT_DISCOUNT,
+        };
+        assert_eq!(
+            discount.weight_factor(before),
+            Some(DEFAULT_REPEAT_DISCOUNT)
+        );
+        assert_eq!(discount.weight_factor(after), Some(1.0));
+    }
+}
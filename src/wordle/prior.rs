@@ -0,0 +1,234 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+///
+/// Decides how likely each word is to be the answer, before any guesses are made. The Solver turns
+/// these weights into probabilities for the words which remain possible.
+///
+/// Weights are relative to each other, and they should be between MIN_WORD_WEIGHT and 1.0 because
+/// some scoring strategies also use the weight of a guess as a bonus (see GuessAnalysis::weight).
+///
+/// Priors must be Send + Sync so that a Solver can be shared between threads.
+///
+pub trait WordPrior: Debug + Send + Sync {
+    /// A short, human readable, name for the prior
+    fn name(&self) -> &'static str;
+
+    /// The weight of each word, parallel to ordered_words (which are sorted from most common to
+    /// least common)
+    fn weights(&self, ordered_words: &[&str]) -> Vec<WordleFloat>;
+}
+
+///
+/// Every word is just as likely to be the answer
+///
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UniformPrior;
+
+impl WordPrior for UniformPrior {
+    fn name(&self) -> &'static str {
+        "uniform"
+    }
+
+    fn weights(&self, ordered_words: &[&str]) -> Vec<WordleFloat> {
+        vec![1.0; ordered_words.len()]
+    }
+}
+
+///
+/// Based on the 3blue1brown implementation, we base the weight on the word's rank.
+///
+/// An arbitrary line called n_common (=2700 by default) is defined. Words with lower ranks (ie;
+/// more common words with rank 0, 1, 2, etc) are considered common, whereas words with ranks higher
+/// than n_common are considered uncommon.
+///
+/// A width is defined, and this is a unitless scaling factor.
+///
+/// A value called "x" is calculated for each word. Imagine this as a position along a sigmoid curve.
+/// The most common word (rank=0) is given an "x" value = width, and words with lower ranks are
+/// linearly spaced such that the word with rank n_common has an "x" value of 0. Words with ranks
+/// lower than n_common continue the same linear spacing into negative numbers off to -inf.
+///
+/// The "x" value is then passed into sigmoid so that it exists between (0.0, 1.0) for all words,
+/// and this is the "weight"
+///
+/// Finally, we use MIN_WORD_WEIGHT when the computed weight is below MIN_WORD_WEIGHT. Words without
+/// frequency data are ranked last, and it is a fair assumption that they are extremely uncommon.
+///
+/// The parameters n_common and width can be tuned to possibly yield better results. Their values
+/// depend on the size of the allowed_words and frequency data file. If you use a different dataset
+/// for word frequency it is recommended to experiment and tune these parameters to this new dataset.
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RankSigmoidPrior {
+    /// The rank at which words stop being considered common (the middle of the sigmoid)
+    pub n_common: WordleFloat,
+    /// How steep the sigmoid is, the "x" value of the most common word
+    pub width: WordleFloat,
+}
+
+impl Default for RankSigmoidPrior {
+    fn default() -> Self {
+        Self {
+            n_common: 2700.0,
+            width: 5.7,
+        }
+    }
+}
+
+impl WordPrior for RankSigmoidPrior {
+    fn name(&self) -> &'static str {
+        "rank sigmoid"
+    }
+
+    fn weights(&self, ordered_words: &[&str]) -> Vec<WordleFloat> {
+        let n_words = ordered_words.len() as WordleFloat;
+
+        // the rank of each word is it's index
+        (0..ordered_words.len())
+            .map(|rank| {
+                let x = ((self.n_common - rank as WordleFloat) / n_words) * self.width;
+                sigmoid(x).max(MIN_WORD_WEIGHT)
+            })
+            .collect()
+    }
+}
+
+///
+/// The weight of each word is proportional to how many times it was seen (like the counts in the
+/// word frequency files). The counts are scaled so that the most common word has a weight of 1.0,
+/// and words which were never seen get MIN_WORD_WEIGHT.
+///
+#[derive(Clone, Debug, Default)]
+pub struct WordCountPrior {
+    counts: HashMap<String, WordleFloat>,
+}
+
+impl WordCountPrior {
+    pub fn new(counts: impl IntoIterator<Item = (String, WordleFloat)>) -> Self {
+        Self {
+            counts: counts.into_iter().collect(),
+        }
+    }
+
+    /// The number of times the word was seen (0 if it never was)
+    pub fn count(&self, word: &str) -> WordleFloat {
+        self.counts.get(word).copied().unwrap_or(0.0)
+    }
+}
+
+impl WordPrior for WordCountPrior {
+    fn name(&self) -> &'static str {
+        "word counts"
+    }
+
+    fn weights(&self, ordered_words: &[&str]) -> Vec<WordleFloat> {
+        let max_count = ordered_words
+            .iter()
+            .map(|word| self.count(word))
+            .fold(0.0, WordleFloat::max);
+
+        ordered_words
+            .iter()
+            .map(|word| {
+                if max_count > 0.0 {
+                    (self.count(word) / max_count).max(MIN_WORD_WEIGHT)
+                } else {
+                    MIN_WORD_WEIGHT
+                }
+            })
+            .collect()
+    }
+}
+
+///
+/// Every word in the answer list is just as likely to be the answer, and every other word gets
+/// MIN_WORD_WEIGHT. Unlike AnswerPool::AnswerList, the other words remain possible (just very
+/// unlikely), so this still works if the list is missing a few answers.
+///
+#[derive(Clone, Debug, Default)]
+pub struct AnswerListPrior {
+    answers: HashSet<String>,
+}
+
+impl AnswerListPrior {
+    pub fn new(answers: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            answers: answers.into_iter().collect(),
+        }
+    }
+}
+
+impl WordPrior for AnswerListPrior {
+    fn name(&self) -> &'static str {
+        "answer list"
+    }
+
+    fn weights(&self, ordered_words: &[&str]) -> Vec<WordleFloat> {
+        ordered_words
+            .iter()
+            .map(|word| {
+                if self.answers.contains(*word) {
+                    1.0
+                } else {
+                    MIN_WORD_WEIGHT
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_priors() {
+        let words = ["there", "about", "crane", "zymic"];
+
+        assert_eq!(UniformPrior.weights(&words), [1.0; 4]);
+
+        let rank = RankSigmoidPrior {
+            n_common: 2.0,
+            width: 4.0,
+        }
+        .weights(&words);
+        assert!(rank.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(rank[2], 0.5);
+
+        let counts = WordCountPrior::new([("there".to_string(), 10.0), ("crane".to_string(), 5.0)])
+            .weights(&words);
+        assert_eq!(counts, [1.0, MIN_WORD_WEIGHT, 0.5, MIN_WORD_WEIGHT]);
+
+        let answers = AnswerListPrior::new(["crane".to_string()]).weights(&words);
+        assert_eq!(
+            answers,
+            [MIN_WORD_WEIGHT, MIN_WORD_WEIGHT, 1.0, MIN_WORD_WEIGHT]
+        );
+    }
+}
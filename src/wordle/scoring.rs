@@ -0,0 +1,492 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use super::{color::*, guess_model::*, prelude::*};
+use std::fmt::Debug;
+
+///
+/// The possible answers, split up by the coloring they would produce for some guess. Each bucket
+/// is indexed by Colorings::to_code, so there is one bucket for each of Colorings::num_states.
+///
+#[derive(Clone, Debug)]
+pub struct ColoringBuckets {
+    /// The probability of seeing each coloring
+    pub probabilities: Vec<WordleFloat>,
+    /// The number of possible answers which produce each coloring
+    pub sizes: Vec<u32>,
+    /// The expected info (in bits) of the colorings which comes from lies instead of from the
+    /// answer (see LieTable::spread). It is 0 when the colorings are honest.
+    pub noise: WordleFloat,
+}
+
+impl ColoringBuckets {
+    ///
+    /// Splits the (word, probability) pairs of all the possible answers into buckets, based on the
+    /// coloring that the guess would get if that word were the answer.
+    ///
+    pub fn new<'w>(guess: &str, answers: impl Iterator<Item = (&'w str, WordleFloat)>) -> Self {
+        let num_colorings = Colorings::num_states(num_letters(guess));
+        Self::from_codes(
+            num_colorings,
+            answers.map(|(answer, probability)| {
+                // determine what coloring we'd get if we used guess & assumed answer=possible_answer
+                // and convert it to a unique index (it's like a base 3 number, see to_code)
+                (
+                    Colorings::with_guess_answer(guess, answer).to_code(),
+                    probability,
+                )
+            }),
+        )
+    }
+
+    ///
+    /// Like new, but the colorings have already been computed (for example, by a ColoringTable).
+    /// Each item is (coloring code, probability of the answer), and num_colorings is the number of
+    /// possible codes (see Colorings::num_states).
+    ///
+    pub fn from_codes(
+        num_colorings: usize,
+        answers: impl Iterator<Item = (ColoringCode, WordleFloat)>,
+    ) -> Self {
+        #[allow(clippy::unnecessary_cast)]
+        let mut out = Self {
+            probabilities: vec![0.0 as WordleFloat; num_colorings],
+            sizes: vec![0; num_colorings],
+            noise: 0.0,
+        };
+
+        for (code, probability) in answers {
+            let bucket_idx = code as usize;
+
+            // we add the weight to the bucket because OR probabilities add (the chance of seeing
+            // a given coloring = chance of word A || chance of word B || ... when A, B, ...
+            // give that coloring)
+            out.probabilities[bucket_idx] += probability;
+            out.sizes[bucket_idx] += 1;
+        }
+
+        out
+    }
+
+    ///
+    /// Determine the average information gained (the sum of p * -p.log2() for all colorings, minus
+    /// the noise)
+    ///
+    pub fn expected_info(&self) -> WordleFloat {
+        #[allow(clippy::unnecessary_cast)]
+        let info: WordleFloat = self
+            .probabilities
+            .iter()
+            // filter non-positive data (aka the 0s) because log2(0) is undefined
+            .filter(|v| *v > &(0.0 as WordleFloat))
+            .map(|v| v * -(v.log2()))
+            .sum();
+        info - self.noise
+    }
+}
+
+///
+/// Everything a ScoringStrategy gets to know about a candidate guess.
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GuessAnalysis {
+    /// The expected info (in bits) of the guess, see Solver::expected_guess_info
+    pub expected_info: WordleFloat,
+    /// The weight of the guess (0 when the guess cannot be the answer)
+    pub weight: WordleFloat,
+    /// The probability that the guess is the answer (0 when the guess cannot be the answer)
+    pub probability: WordleFloat,
+    /// How many possible answers are in the largest coloring bucket (the worst case)
+    pub largest_bucket_size: usize,
+    /// The probability of the most likely coloring
+    pub largest_bucket_probability: WordleFloat,
+    /// The number of possible answers we expect to remain after making the guess
+    pub expected_remaining: WordleFloat,
+    /// The entropy of the puzzle before the guess is made (see Solver::remaining_entropy)
+    pub remaining_entropy: WordleFloat,
+    /// Estimates how many guesses the entropy left after the guess will cost (see
+    /// Solver::guess_count_model)
+    pub guess_count_model: GuessCountModel,
+}
+
+impl GuessAnalysis {
+    pub fn new(
+        buckets: &ColoringBuckets,
+        weight: WordleFloat,
+        probability: WordleFloat,
+        remaining_entropy: WordleFloat,
+        guess_count_model: GuessCountModel,
+    ) -> Self {
+        #[allow(clippy::unnecessary_cast)]
+        let zero = 0.0 as WordleFloat;
+        Self {
+            expected_info: buckets.expected_info(),
+            weight,
+            probability,
+            largest_bucket_size: buckets.sizes.iter().copied().max().unwrap_or(0) as usize,
+            largest_bucket_probability: buckets
+                .probabilities
+                .iter()
+                .copied()
+                .fold(zero, WordleFloat::max),
+            expected_remaining: buckets
+                .probabilities
+                .iter()
+                .zip(buckets.sizes.iter())
+                .map(|(p, size)| p * (*size as WordleFloat))
+                .sum(),
+            remaining_entropy,
+            guess_count_model,
+        }
+    }
+}
+
+///
+/// Decides how candidate guesses are ranked by the Solver. Higher scores are better.
+///
+/// Strategies must be Send + Sync so that a Solver can be shared between threads.
+///
+pub trait ScoringStrategy: Debug + Send + Sync {
+    /// A unique identifier for the strategy (no spaces), used to store cached data
+    fn id(&self) -> &'static str;
+
+    /// A short, human readable, name for the strategy
+    fn name(&self) -> &'static str;
+
+    /// Compute the score ("abs") for a guess. Higher scores are recommended first.
+    fn score(&self, analysis: &GuessAnalysis) -> WordleFloat;
+
+    /// When two guesses have the same score, the one with the higher tie_break is recommended first
+    fn tie_break(&self, _analysis: &GuessAnalysis) -> WordleFloat {
+        0.0
+    }
+
+    /// Whether or not the exact endgame search (which minimizes the expected number of guesses)
+    /// should rank the candidates instead of this strategy, once few possibilities remain
+    fn wants_endgame(&self) -> bool {
+        false
+    }
+}
+
+///
+/// The original scoring: expected info (in bits) plus the weight of the word. The weight acts as
+/// a bonus for words which are common, and therefore likely to be the answer.
+///
+/// This is the default strategy. Like every built-in strategy, its default state scores are
+/// cached (see gen_all_data), so the first recommendations don't need to be computed.
+///
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EntropyPlusWeight;
+
+impl ScoringStrategy for EntropyPlusWeight {
+    fn id(&self) -> &'static str {
+        "entropy_weight"
+    }
+
+    fn name(&self) -> &'static str {
+        "entropy + weight"
+    }
+
+    fn score(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        analysis.expected_info + analysis.weight
+    }
+
+    fn wants_endgame(&self) -> bool {
+        true
+    }
+}
+
+///
+/// Only considers expected info, ignoring how likely the guess is to be the answer
+///
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Entropy;
+
+impl ScoringStrategy for Entropy {
+    fn id(&self) -> &'static str {
+        "entropy"
+    }
+
+    fn name(&self) -> &'static str {
+        "entropy"
+    }
+
+    fn score(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        analysis.expected_info
+    }
+}
+
+///
+/// Minimizes the worst case: the guess with the smallest "largest coloring bucket" is ranked
+/// highest. This is for players who care more about never failing than about the average number of
+/// guesses. Ties are broken using expected info.
+///
+/// The endgame search minimizes the expected number of guesses instead, so it isn't used for this
+/// strategy (see ScoringStrategy::wants_endgame): the worst case matters most near the end.
+///
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Minimax {
+    /// How the size of the worst case bucket is measured
+    pub measure: WorstCaseMeasure,
+}
+
+///
+/// The two ways to measure the "size" of a coloring bucket for Minimax
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WorstCaseMeasure {
+    /// The number of possible answers in the bucket
+    #[default]
+    Size,
+    /// The total probability of the possible answers in the bucket
+    Probability,
+}
+
+impl Minimax {
+    pub fn new(measure: WorstCaseMeasure) -> Self {
+        Self { measure }
+    }
+}
+
+impl ScoringStrategy for Minimax {
+    fn id(&self) -> &'static str {
+        match self.measure {
+            WorstCaseMeasure::Size => "minimax",
+            WorstCaseMeasure::Probability => "minimax_probability",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.measure {
+            WorstCaseMeasure::Size => "minimax",
+            WorstCaseMeasure::Probability => "minimax (probability)",
+        }
+    }
+
+    fn score(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        match self.measure {
+            WorstCaseMeasure::Size => -(analysis.largest_bucket_size as WordleFloat),
+            WorstCaseMeasure::Probability => -analysis.largest_bucket_probability,
+        }
+    }
+
+    fn tie_break(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        analysis.expected_info
+    }
+}
+
+///
+/// Minimizes the number of possible answers we expect to remain after the guess
+///
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ExpectedRemaining;
+
+impl ScoringStrategy for ExpectedRemaining {
+    fn id(&self) -> &'static str {
+        "expected_remaining"
+    }
+
+    fn name(&self) -> &'static str {
+        "expected remaining"
+    }
+
+    fn score(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        -analysis.expected_remaining
+    }
+}
+
+///
+/// Always guesses the word which is most likely to be the answer, ignoring information entirely
+///
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MostLikely;
+
+impl ScoringStrategy for MostLikely {
+    fn id(&self) -> &'static str {
+        "most_likely"
+    }
+
+    fn name(&self) -> &'static str {
+        "most likely answer"
+    }
+
+    fn score(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        analysis.probability
+    }
+}
+
+///
+/// Minimizes the expected number of guesses needed to solve the puzzle, using a GuessCountModel to
+/// estimate how many guesses the entropy left over after a guess will cost. This is the scoring
+/// used by 3blue1brown's second version of the solver:
+///
+///   E[guesses] = p * 1 + (1 - p) * (1 + f(H - expected_info))
+///
+/// Where p is the probability that the guess is the answer, H is the entropy before the guess and
+/// f is the Solver's GuessCountModel (see GuessAnalysis::guess_count_model). The score is
+/// -E[guesses], so ties are broken using expected info.
+///
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ExpectedGuesses;
+
+impl ExpectedGuesses {
+    ///
+    /// The expected number of guesses (including this one) to solve the puzzle after making the
+    /// guess described by the analysis.
+    ///
+    pub fn expected_guesses(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        let p = analysis.probability;
+        let entropy_after = analysis.remaining_entropy - analysis.expected_info;
+        p + (1.0 - p) * (1.0 + analysis.guess_count_model.expected_guesses(entropy_after))
+    }
+}
+
+impl ScoringStrategy for ExpectedGuesses {
+    fn id(&self) -> &'static str {
+        "expected_guesses"
+    }
+
+    fn name(&self) -> &'static str {
+        "expected guesses"
+    }
+
+    fn score(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        -self.expected_guesses(analysis)
+    }
+
+    fn tie_break(&self, analysis: &GuessAnalysis) -> WordleFloat {
+        analysis.expected_info
+    }
+
+    /// The endgame search finds the exact number of guesses which this strategy estimates
+    fn wants_endgame(&self) -> bool {
+        true
+    }
+}
+
+///
+/// Returns one of each of the built-in strategies (with the default strategy first), which is
+/// useful for comparing them or letting a user pick one.
+///
+pub fn builtin_scoring_strategies() -> Vec<Box<dyn ScoringStrategy>> {
+    vec![
+        Box::new(EntropyPlusWeight),
+        Box::new(Entropy),
+        Box::new(Minimax::new(WorstCaseMeasure::Size)),
+        Box::new(Minimax::new(WorstCaseMeasure::Probability)),
+        Box::new(ExpectedRemaining),
+        Box::new(MostLikely),
+        Box::new(ExpectedGuesses),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_analysis_from_buckets() {
+        let answers = [
+            ("batch", 0.25),
+            ("catch", 0.25),
+            ("hatch", 0.25),
+            ("watch", 0.25),
+        ];
+        let buckets = ColoringBuckets::new("chomp", answers.iter().copied());
+        let analysis = GuessAnalysis::new(&buckets, 0.0, 0.0, 1.0, GuessCountModel::default());
+
+        // chomp gives the same coloring for batch, hatch & watch, but catch has a green c
+        assert_eq!(analysis.largest_bucket_size, 3);
+        assert!((analysis.largest_bucket_probability - 0.75).abs() < 0.000001);
+        assert!((analysis.expected_remaining - 2.5).abs() < 0.000001);
+        let expected_info = 0.75 * -(0.75 as WordleFloat).log2() + 0.25 * 2.0;
+        assert!((analysis.expected_info - expected_info).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_minimax_breaks_ties_with_expected_info() {
+        let analysis = GuessAnalysis {
+            expected_info: 2.0,
+            weight: 0.0,
+            probability: 0.0,
+            largest_bucket_size: 10,
+            largest_bucket_probability: 0.3,
+            expected_remaining: 4.0,
+            remaining_entropy: 5.0,
+            guess_count_model: GuessCountModel::default(),
+        };
+        let more_info = GuessAnalysis {
+            expected_info: 3.0,
+            largest_bucket_probability: 0.2,
+            ..analysis
+        };
+
+        let by_size = Minimax::new(WorstCaseMeasure::Size);
+        assert_eq!(by_size.score(&analysis), by_size.score(&more_info));
+        assert!(by_size.tie_break(&analysis) < by_size.tie_break(&more_info));
+
+        let by_probability = Minimax::new(WorstCaseMeasure::Probability);
+        assert!(by_probability.score(&analysis) < by_probability.score(&more_info));
+    }
+
+    #[test]
+    fn test_expected_guesses_strategy() {
+        let strategy = ExpectedGuesses;
+        let analysis = GuessAnalysis {
+            expected_info: 2.0,
+            weight: 0.0,
+            probability: 0.25,
+            largest_bucket_size: 10,
+            largest_bucket_probability: 0.3,
+            expected_remaining: 4.0,
+            remaining_entropy: 5.0,
+            guess_count_model: GuessCountModel {
+                coefficients: [1.0, 1.0, 0.0],
+            },
+        };
+
+        // 0.25 * 1 + 0.75 * (1 + f(5 - 2))
+        let expected = 0.25 + 0.75 * (1.0 + 4.0);
+        assert!((strategy.expected_guesses(&analysis) - expected).abs() < 0.000001);
+        assert_eq!(
+            strategy.score(&analysis),
+            -strategy.expected_guesses(&analysis)
+        );
+    }
+
+    #[test]
+    fn test_builtin_strategy_ids_are_unique() {
+        let strategies = builtin_scoring_strategies();
+        for (idx, strategy) in strategies.iter().enumerate() {
+            assert!(
+                !strategy.id().contains(' '),
+                "{} has a space",
+                strategy.id()
+            );
+            assert!(strategies[..idx]
+                .iter()
+                .all(|other| other.id() != strategy.id()));
+        }
+    }
+}
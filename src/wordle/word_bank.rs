@@ -0,0 +1,281 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2022 Joseph Sacchini
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use std::collections::HashMap;
+
+///
+/// Identifies a word in a WordBank. The ids are dense (0..len) and follow the order of the word
+/// list the WordBank was created with, which is the rank order produced by gen_all_data.
+///
+pub type WordId = u16;
+
+///
+/// Gives every word a WordId, so that sets of words can be stored as bitsets (see WordSet) and data
+/// about each word can be stored in a Vec indexed by id, instead of in HashMaps keyed by &str.
+///
+/// Converting between &str and WordId should only happen at the "boundary" (user input and output).
+///
+#[derive(Clone, Debug)]
+pub struct WordBank<'a> {
+    words: Vec<&'a str>,
+    ids: HashMap<&'a str, WordId>,
+}
+
+impl<'a> WordBank<'a> {
+    /// The largest number of words which can be given an id
+    pub const MAX_WORDS: usize = WordId::MAX as usize + 1;
+
+    ///
+    /// Creates a WordBank where the id of each word is it's index in ordered_words. Panics if
+    /// there are more than MAX_WORDS words.
+    ///
+    pub fn new(ordered_words: &'a [String]) -> Self {
+        assert!(
+            ordered_words.len() <= Self::MAX_WORDS,
+            "a word bank can hold at most {} words, got {}",
+            Self::MAX_WORDS,
+            ordered_words.len()
+        );
+
+        let words: Vec<&'a str> = ordered_words.iter().map(|w| w.as_str()).collect();
+        let ids = words
+            .iter()
+            .enumerate()
+            .map(|(id, word)| (*word, id as WordId))
+            .collect();
+
+        Self { words, ids }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    ///
+    /// Looks up the id of a word, None if the word is not in this bank
+    ///
+    pub fn id(&self, word: &str) -> Option<WordId> {
+        self.ids.get(word).copied()
+    }
+
+    ///
+    /// Looks up the word with the given id. Panics if the id did not come from this bank.
+    ///
+    pub fn word(&self, id: WordId) -> &'a str {
+        self.words[id as usize]
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.ids.contains_key(word)
+    }
+
+    ///
+    /// All of the words, indexed by id
+    ///
+    pub fn words(&self) -> &[&'a str] {
+        &self.words
+    }
+
+    ///
+    /// All of the ids, in order
+    ///
+    pub fn ids(&self) -> impl Iterator<Item = WordId> {
+        (0..self.len()).map(|id| id as WordId)
+    }
+}
+
+///
+/// A set of WordIds, stored as a bitset with one bit for every word in a WordBank
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordSet {
+    blocks: Vec<u64>,
+    capacity: usize,
+    len: usize,
+}
+
+impl WordSet {
+    const BLOCK_BITS: usize = u64::BITS as usize;
+
+    ///
+    /// Creates a set which can hold the ids 0..capacity, with none of them in the set
+    ///
+    pub fn empty(capacity: usize) -> Self {
+        Self {
+            blocks: vec![0; capacity.div_ceil(Self::BLOCK_BITS)],
+            capacity,
+            len: 0,
+        }
+    }
+
+    ///
+    /// Creates a set containing all of the ids 0..capacity
+    ///
+    pub fn full(capacity: usize) -> Self {
+        let mut out = Self::empty(capacity);
+        out.fill();
+        out
+    }
+
+    ///
+    /// Adds every id (in 0..capacity) to the set
+    ///
+    pub fn fill(&mut self) {
+        self.blocks.fill(u64::MAX);
+        let extra_bits = self.blocks.len() * Self::BLOCK_BITS - self.capacity;
+        if let Some(last) = self.blocks.last_mut() {
+            *last >>= extra_bits;
+        }
+        self.len = self.capacity;
+    }
+
+    pub fn clear(&mut self) {
+        self.blocks.fill(0);
+        self.len = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, id: WordId) -> bool {
+        let (block, mask) = Self::locate(id);
+        self.blocks
+            .get(block)
+            .map(|b| b & mask != 0)
+            .unwrap_or(false)
+    }
+
+    ///
+    /// Adds the id to the set, returns true if it was not already in the set
+    ///
+    pub fn insert(&mut self, id: WordId) -> bool {
+        assert!((id as usize) < self.capacity, "id {} is out of range", id);
+        let (block, mask) = Self::locate(id);
+        let added = self.blocks[block] & mask == 0;
+        self.blocks[block] |= mask;
+        self.len += added as usize;
+        added
+    }
+
+    ///
+    /// Removes the id from the set, returns true if it was in the set
+    ///
+    pub fn remove(&mut self, id: WordId) -> bool {
+        let removed = self.contains(id);
+        if removed {
+            let (block, mask) = Self::locate(id);
+            self.blocks[block] &= !mask;
+            self.len -= 1;
+        }
+        removed
+    }
+
+    ///
+    /// Removes all of the ids for which the predicate returns false
+    ///
+    pub fn retain(&mut self, mut predicate: impl FnMut(WordId) -> bool) {
+        let to_remove: Vec<WordId> = self.iter().filter(|id| !predicate(*id)).collect();
+        for id in to_remove {
+            self.remove(id);
+        }
+    }
+
+    ///
+    /// Iterates through the ids in the set, in order
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = WordId> + '_ {
+        self.blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(block_idx, block)| {
+                let mut remaining = *block;
+                std::iter::from_fn(move || {
+                    if remaining == 0 {
+                        return None;
+                    }
+
+                    let bit = remaining.trailing_zeros() as usize;
+                    // clear the lowest set bit
+                    remaining &= remaining - 1;
+                    Some((block_idx * Self::BLOCK_BITS + bit) as WordId)
+                })
+            })
+    }
+
+    fn locate(id: WordId) -> (usize, u64) {
+        let id = id as usize;
+        (id / Self::BLOCK_BITS, 1 << (id % Self::BLOCK_BITS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_bank_ids() {
+        let words: Vec<String> = ["tares", "lares", "rates"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let bank = WordBank::new(&words);
+        assert_eq!(bank.len(), 3);
+        assert_eq!(bank.id("lares"), Some(1));
+        assert_eq!(bank.id("latch"), None);
+        assert_eq!(bank.word(2), "rates");
+        assert_eq!(bank.ids().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_word_set() {
+        let mut set = WordSet::full(130);
+        assert_eq!(set.len(), 130);
+        assert_eq!(set.iter().count(), 130);
+        assert!(set.contains(129));
+        assert!(!set.contains(130));
+
+        set.retain(|id| id % 64 == 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 67]);
+        assert_eq!(set.len(), 2);
+
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert!(set.insert(129));
+        assert!(!set.insert(129));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![67, 129]);
+
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.iter().next(), None);
+    }
+}